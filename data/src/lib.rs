@@ -11,7 +11,12 @@
 // 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
 // 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
 
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use std::io::{Error, ErrorKind};
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 /*************************************************************************************************
  *                                             Types                                             *
@@ -53,7 +58,19 @@ pub const SRATIONAL: u16 = 10;
 pub const FLOAT: u16 = 11;
 pub const DOUBLE: u16 = 12;
 
-// ASCII maps to String; RATIONAL and SRATIONAL, to their own types
+// TIFF Technical Note 1, TIFF Trees, adds type 13, IFD: a 32-bit offset to a child IFD, which
+// SubIFDs and the like may use instead of LONG. BigTIFF adds types 16, LONG8, 17, SLONG8, and 18,
+// IFD8: their 64-bit counterparts.
+pub const IFD: u16 = 13;
+pub const LONG8: u16 = 16;
+pub const SLONG8: u16 = 17;
+pub const IFD8: u16 = 18;
+
+// Exif 3.0 adds type 129, UTF-8: 8-bit bytes holding a NUL terminated UTF-8 string. It can be used
+// instead of ASCII by text fields such as Artist and Copyright.
+pub const UTF8: u16 = 129;
+
+// ASCII and UTF8 map to String; RATIONAL and SRATIONAL, to their own types
 pub type Byte = u8;
 pub type Short = u16;
 pub type Long = u32;
@@ -61,6 +78,8 @@ pub type Sbyte = i8;
 pub type Undefined = u8;
 pub type Sshort = i16;
 pub type Slong = i32;
+pub type Long8 = u64;
+pub type Slong8 = i64;
 pub type Float = f32;
 pub type Double = f64;
 
@@ -73,10 +92,10 @@ pub fn type_size(type_: u16) -> Result<usize, Error> {
             ErrorKind::InvalidData,
             format!("Not a valid type: {type_}"),
         )),
-        ASCII | BYTE | SBYTE | UNDEFINED => Ok(1),
+        ASCII | BYTE | SBYTE | UNDEFINED | UTF8 => Ok(1),
         SHORT | SSHORT => Ok(2),
-        FLOAT | LONG | SLONG => Ok(4),
-        DOUBLE | RATIONAL | SRATIONAL => Ok(8),
+        FLOAT | LONG | SLONG | IFD => Ok(4),
+        DOUBLE | RATIONAL | SRATIONAL | LONG8 | SLONG8 | IFD8 => Ok(8),
         // Should not err on this, but skip
         _ => Err(Error::new(
             ErrorKind::InvalidData,
@@ -89,15 +108,19 @@ pub fn type_size(type_: u16) -> Result<usize, Error> {
  *                                              Tags                                             *
  *************************************************************************************************/
 
-#[derive(Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Tag {
-    Unknown,
+    // Tags this crate does not know about keep their number, so that several of them can coexist
+    // in the same IFD and callers can still inspect vendor-private tags.
+    Other(u16),
     NewSubFileType,
     ImageWidth,
     ImageLength,
     BitsPerSample,
     Compression,
     PhotometricInterpretation,
+    FillOrder,
+    ImageDescription,
     Make,
     Model,
     StripOffsets,
@@ -105,20 +128,127 @@ pub enum Tag {
     SamplesPerPixel,
     RowsPerStrip,
     StripByteCounts,
+    XResolution,
+    YResolution,
     PlanarConfiguration,
+    ResolutionUnit,
+    TransferFunction,
     Software,
     DateTime,
     Artist,
+    WhitePoint,
+    PrimaryChromaticities,
+    TileWidth,
+    TileLength,
+    TileOffsets,
+    TileByteCounts,
     SubIFDs,
+    JPEGInterchangeFormat,
+    JPEGInterchangeFormatLength,
+    YCbCrCoefficients,
+    YCbCrSubSampling,
+    YCbCrPositioning,
+    ReferenceBlackWhite,
     XMP,
+    CFARepeatPatternDim,
+    CFAPattern,
     Copyright,
+    ExposureTime,
+    FNumber,
+    IPTCNAA,
+    Photoshop,
     ExifIFD,
+    InterColorProfile,
+    ExposureProgram,
+    SpectralSensitivity,
+    GPSInfo,
+    ISOSpeedRatings,
+    OECF,
+    SensitivityType,
+    StandardOutputSensitivity,
+    RecommendedExposureIndex,
+    ISOSpeed,
+    ISOSpeedLatitudeyyy,
+    ISOSpeedLatitudezzz,
+    ExifVersion,
+    DateTimeOriginal,
+    DateTimeDigitized,
+    OffsetTime,
+    OffsetTimeOriginal,
+    OffsetTimeDigitized,
+    ComponentsConfiguration,
+    CompressedBitsPerPixel,
+    ShutterSpeedValue,
+    ApertureValue,
+    BrightnessValue,
+    ExposureBiasValue,
+    MaxApertureValue,
+    SubjectDistance,
+    MeteringMode,
+    LightSource,
+    Flash,
+    FocalLength,
     ImageNumber,
+    SubjectArea,
+    MakerNote,
+    UserComment,
+    SubSecTime,
+    SubSecTimeOriginal,
+    SubSecTimeDigitized,
+    Temperature,
+    Humidity,
+    Pressure,
+    WaterDepth,
+    Acceleration,
+    CameraElevationAngle,
+    FlashpixVersion,
+    ColorSpace,
+    PixelXDimension,
+    PixelYDimension,
+    RelatedSoundFile,
+    InteroperabilityIFD,
+    FlashEnergy,
+    SpatialFrequencyResponse,
+    FocalPlaneXResolution,
+    FocalPlaneYResolution,
+    FocalPlaneResolutionUnit,
+    SubjectLocation,
+    ExposureIndex,
+    SensingMethod,
+    FileSource,
+    SceneType,
+    ExifCFAPattern,
+    CustomRendered,
+    ExposureMode,
+    WhiteBalance,
+    DigitalZoomRatio,
+    FocalLengthIn35mmFilm,
+    SceneCaptureType,
+    GainControl,
+    Contrast,
+    Saturation,
+    Sharpness,
+    DeviceSettingDescription,
+    SubjectDistanceRange,
+    ImageUniqueID,
+    CameraOwnerName,
+    BodySerialNumber,
+    LensSpecification,
+    LensMake,
+    LensModel,
+    LensSerialNumber,
+    Gamma,
     DNGVersion,
     DNGBackwardVersion,
     UniqueCameraModel,
     LocalizedCameraModel,
+    CFAPlaneColor,
+    CFALayout,
+    LinearizationTable,
+    BlackLevelRepeatDim,
     BlackLevel,
+    BlackLevelDeltaH,
+    BlackLevelDeltaV,
     WhiteLevel,
     DefaultScale,
     DefaultCropOrigin,
@@ -127,8 +257,11 @@ pub enum Tag {
     ColorMatrix2,
     CameraCalibration1,
     CameraCalibration2,
+    ReductionMatrix1,
+    ReductionMatrix2,
     AnalogBalance,
     AsShotNeutral,
+    AsShotWhiteXY,
     BaselineExposure,
     BaselineNoise,
     BaselineSharpness,
@@ -136,254 +269,1983 @@ pub enum Tag {
     LinearResponseLimit,
     CameraSerialNumber,
     LensInfo,
+    ChromaBlurRadius,
     AntiAliasStrength,
     ShadowScale,
     DNGPrivateData,
+    MakerNoteSafety,
     CalibrationIlluminant1,
     CalibrationIlluminant2,
     BestQualityScale,
     RawDataUniqueID,
     OriginalRawFileName,
+    OriginalRawFileData,
+    ActiveArea,
+    MaskedAreas,
+    AsShotICCProfile,
+    AsShotPreProfileMatrix,
+    CurrentICCProfile,
+    CurrentPreProfileMatrix,
+    ColorimetricReference,
     CameraCalibrationSignature,
     ProfileCalibrationSignature,
+    ExtraCameraProfiles,
+    AsShotProfileName,
+    NoiseReductionApplied,
     ProfileName,
+    ProfileHueSatMapDims,
+    ProfileHueSatMapData1,
+    ProfileHueSatMapData2,
+    ProfileToneCurve,
     ProfileEmbedPolicy,
     ProfileCopyright,
     ForwardMatrix1,
     ForwardMatrix2,
     PreviewApplicationName,
     PreviewApplicationVersion,
+    PreviewSettingsName,
     PreviewSettingsDigest,
     PreviewColorSpace,
     PreviewDateTime,
     RawImageDigest,
+    OriginalRawFileDigest,
+    SubTileBlockSize,
+    RowInterleaveFactor,
+    ProfileLookTableDims,
+    ProfileLookTableData,
+    OpcodeList1,
+    OpcodeList2,
+    OpcodeList3,
     NoiseProfile,
+    OriginalDefaultFinalSize,
+    OriginalBestQualityFinalSize,
+    OriginalDefaultCropSize,
+    ProfileHueSatMapEncoding,
+    ProfileLookTableEncoding,
+    BaselineExposureOffset,
+    DefaultBlackRender,
+    NewRawImageDigest,
+    RawToPreviewGain,
+    DefaultUserCrop,
+    DepthFormat,
+    DepthNear,
+    DepthFar,
+    DepthUnits,
+    DepthMeasureType,
+    EnhanceParams,
+    ProfileGainTableMap,
+    SemanticName,
+    SemanticInstanceID,
+    CalibrationIlluminant3,
+    CameraCalibration3,
+    ColorMatrix3,
+    ForwardMatrix3,
+    IlluminantData1,
+    IlluminantData2,
+    IlluminantData3,
+    MaskSubArea,
+    ProfileHueSatMapData3,
+    ReductionMatrix3,
+    RGBTables,
+    ProfileGainTableMap2,
+    ColumnInterleaveFactor,
+    ImageSequenceInfo,
+    ImageStats,
+    ProfileDynamicRange,
+    ProfileGroupName,
+    JXLDistance,
+    JXLEffort,
+    JXLDecodeSpeed,
+
+    // GPS tags, only found in the GPS IFD
+    GPSVersionID,
+    GPSLatitudeRef,
+    GPSLatitude,
+    GPSLongitudeRef,
+    GPSLongitude,
+    GPSAltitudeRef,
+    GPSAltitude,
+    GPSTimeStamp,
+    GPSSatellites,
+    GPSStatus,
+    GPSMeasureMode,
+    GPSDOP,
+    GPSSpeedRef,
+    GPSSpeed,
+    GPSTrackRef,
+    GPSTrack,
+    GPSImgDirectionRef,
+    GPSImgDirection,
+    GPSMapDatum,
+    GPSDestLatitudeRef,
+    GPSDestLatitude,
+    GPSDestLongitudeRef,
+    GPSDestLongitude,
+    GPSDestBearingRef,
+    GPSDestBearing,
+    GPSDestDistanceRef,
+    GPSDestDistance,
+    GPSProcessingMethod,
+    GPSAreaInformation,
+    GPSDateStamp,
+    GPSDifferential,
+    GPSHPositioningError,
+
+    // Interoperability tags, only found in the Interoperability IFD
+    InteroperabilityIndex,
+    InteroperabilityVersion,
+    RelatedImageFileFormat,
+    RelatedImageWidth,
+    RelatedImageLength,
 }
 
-impl Tag {
-    #[must_use]
-    pub const fn new(tag: u16) -> Tag {
-        match tag {
-            // Digital Negative Specification, Version 1.4.0.0, page 18
-            254 => Tag::NewSubFileType,
-
-            // TIFF 6.0 Specification, page 18
-            256 => Tag::ImageWidth,
-
-            // TIFF 6.0 Specification, page 18
-            257 => Tag::ImageLength,
-
-            // TIFF 6.0 Specification, page 29
-            258 => Tag::BitsPerSample,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 19
-            259 => Tag::Compression,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 20
-            262 => Tag::PhotometricInterpretation,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 35
-            271 => Tag::Make,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 35
-            272 => Tag::Model,
-
-            // TIFF 6.0 Specification, page 19
-            273 => Tag::StripOffsets,
-
-            // TIFF 6.0 Specification, page 36
-            // TIFF/EP, page 23
-            // Digital Negative Specification, Version 1.4.0.0, page 20
-            274 => Tag::Orientation,
-
-            // TIFF 6.0 Specification, page 39
-            277 => Tag::SamplesPerPixel,
-
-            // TIFF 6.0 Specification, page 19
-            278 => Tag::RowsPerStrip,
-
-            // TIFF 6.0 Specification, page 19
-            279 => Tag::StripByteCounts,
-
-            // TIFF 6.0 Specification, page 19
-            284 => Tag::PlanarConfiguration,
-
-            // TIFF 6.0 Specification, page 39
-            305 => Tag::Software,
-
-            // TIFF 6.0 Specification, page 31
-            306 => Tag::DateTime,
-
-            // TIFF 6.0 Specification, page 28
-            315 => Tag::Artist,
-
-            // TIFF/EP, page 21
-            330 => Tag::SubIFDs,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 14
-            700 => Tag::XMP,
-
-            33432 => Tag::Copyright,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 14
-            34665 => Tag::ExifIFD,
-
-            37393 => Tag::ImageNumber,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 22
-            50706 => Tag::DNGVersion,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 22
-            50707 => Tag::DNGBackwardVersion,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 23
-            50708 => Tag::UniqueCameraModel,
-
-            50709 => Tag::LocalizedCameraModel,
-
-            // Digital Negative Specification, Version 1.4.0.0,  page 27
-            // See chapter 5, “Mapping Raw Values to Linear Reference Values” on page 77 for
-            // details of the processing model.
-            50714 => Tag::BlackLevel,
-
-            // Digital Negative Specification, Version 1.4.0.0,  page 29
-            // See chapter 5, “Mapping Raw Values to Linear Reference Values” on page 77 for
-            // details of the processing model.
-            50717 => Tag::WhiteLevel,
-
-            // Digital Negative Specification, Version 1.4.0.0,  page 29
-            50718 => Tag::DefaultScale,
-
-            // Digital Negative Specification, Version 1.4.0.0,  page 30
-            50719 => Tag::DefaultCropOrigin,
-
-            // Digital Negative Specification, Version 1.4.0.0,  page 31
-            // http://www.barrypearson.co.uk/articles/dng/specification.htm
-            50720 => Tag::DefaultCropSize,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 32
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            50721 => Tag::ColorMatrix1,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 33
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            50722 => Tag::ColorMatrix2,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 34
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            50723 => Tag::CameraCalibration1,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 34
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            50724 => Tag::CameraCalibration2,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 36
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            50727 => Tag::AnalogBalance,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 37
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            50728 => Tag::AsShotNeutral,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 38
-            50730 => Tag::BaselineExposure,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 38
-            50731 => Tag::BaselineNoise,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 39
-            50732 => Tag::BaselineSharpness,
-
-            50733 => Tag::BayerGreenSplit,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 40
-            50734 => Tag::LinearResponseLimit,
-
-            50735 => Tag::CameraSerialNumber,
-
-            50736 => Tag::LensInfo,
-
-            // Digital Negative Specification, Version 1.4.0.0, page 42
-            50738 => Tag::AntiAliasStrength,
+// Numbers of the tags this crate knows about, one table per numbering, each sorted by number.
+// Tag::id() is made from the same lists, so numbers are written once and cannot drift apart, and
+// leaving a tag out of every table does not compile.
+macro_rules! numberings {
+    ($($table:ident = $namespace:literal [$(($id:literal, Tag::$tag:ident)),* $(,)?])*) => {
+        $(const $table: &[(u16, Tag)] = &[$(($id, Tag::$tag)),*];)*
+
+        impl Tag {
+            /// Numeric value of the tag, as found in the file
+            #[must_use]
+            pub const fn id(&self) -> u16 {
+                match self {
+                    Tag::Other(id) => *id,
+                    $($(Tag::$tag => $id,)*)*
+                }
+            }
+
+            // Tags from different numberings share numbers, this tells them apart when ordering:
+            // 0 for TIFF, Exif and DNG tags, 1 for GPS ones and 2 for Interoperability ones
+            const fn namespace(self) -> u8 {
+                match self {
+                    Tag::Other(_) => 0,
+                    $($(Tag::$tag => $namespace,)*)*
+                }
+            }
+        }
+    };
+}
 
-            // Digital Negative Specification, Version 1.4.0.0, page 42
-            50739 => Tag::ShadowScale,
+numberings! {
+    // Every tag this crate knows about, sorted by number
+    TAGS = 0 [
+        // Digital Negative Specification, Version 1.4.0.0, page 18
+        (254, Tag::NewSubFileType),
+        // TIFF 6.0 Specification, page 18
+        (256, Tag::ImageWidth),
+        // TIFF 6.0 Specification, page 18
+        (257, Tag::ImageLength),
+        // TIFF 6.0 Specification, page 29
+        (258, Tag::BitsPerSample),
+        // Digital Negative Specification, Version 1.4.0.0, page 19
+        (259, Tag::Compression),
+        // Digital Negative Specification, Version 1.4.0.0, page 20
+        (262, Tag::PhotometricInterpretation),
+        // TIFF 6.0 Specification, page 32
+        (266, Tag::FillOrder),
+        (270, Tag::ImageDescription),
+        // Digital Negative Specification, Version 1.4.0.0, page 35
+        (271, Tag::Make),
+        // Digital Negative Specification, Version 1.4.0.0, page 35
+        (272, Tag::Model),
+        // TIFF 6.0 Specification, page 19
+        (273, Tag::StripOffsets),
+        // TIFF 6.0 Specification, page 36
+        // TIFF/EP, page 23
+        // Digital Negative Specification, Version 1.4.0.0, page 20
+        (274, Tag::Orientation),
+        // TIFF 6.0 Specification, page 39
+        (277, Tag::SamplesPerPixel),
+        // TIFF 6.0 Specification, page 19
+        (278, Tag::RowsPerStrip),
+        // TIFF 6.0 Specification, page 19
+        (279, Tag::StripByteCounts),
+        (282, Tag::XResolution),
+        (283, Tag::YResolution),
+        // TIFF 6.0 Specification, page 19
+        (284, Tag::PlanarConfiguration),
+        (296, Tag::ResolutionUnit),
+        (301, Tag::TransferFunction),
+        // TIFF 6.0 Specification, page 39
+        (305, Tag::Software),
+        // TIFF 6.0 Specification, page 31
+        (306, Tag::DateTime),
+        // TIFF 6.0 Specification, page 28
+        (315, Tag::Artist),
+        (318, Tag::WhitePoint),
+        (319, Tag::PrimaryChromaticities),
+        // TIFF 6.0 Specification, Section 15: Tiled Images
+        (322, Tag::TileWidth),
+        (323, Tag::TileLength),
+        (324, Tag::TileOffsets),
+        (325, Tag::TileByteCounts),
+        // TIFF/EP, page 21
+        (330, Tag::SubIFDs),
+        (513, Tag::JPEGInterchangeFormat),
+        (514, Tag::JPEGInterchangeFormatLength),
+        (529, Tag::YCbCrCoefficients),
+        (530, Tag::YCbCrSubSampling),
+        (531, Tag::YCbCrPositioning),
+        (532, Tag::ReferenceBlackWhite),
+        // Digital Negative Specification, Version 1.4.0.0, page 14
+        (700, Tag::XMP),
+        (33421, Tag::CFARepeatPatternDim),
+        (33422, Tag::CFAPattern),
+        (33432, Tag::Copyright),
+        (33434, Tag::ExposureTime),
+        (33437, Tag::FNumber),
+        // IPTC - NAA Information Interchange Model, Version 4
+        (33723, Tag::IPTCNAA),
+        // Adobe Photoshop TIFF Technical Notes
+        (34377, Tag::Photoshop),
+        // Digital Negative Specification, Version 1.4.0.0, page 14
+        (34665, Tag::ExifIFD),
+        // ICC.1:2010, Annex B.4
+        (34675, Tag::InterColorProfile),
+        (34850, Tag::ExposureProgram),
+        (34852, Tag::SpectralSensitivity),
+        (34853, Tag::GPSInfo),
+        (34855, Tag::ISOSpeedRatings),
+        (34856, Tag::OECF),
+        (34864, Tag::SensitivityType),
+        (34865, Tag::StandardOutputSensitivity),
+        (34866, Tag::RecommendedExposureIndex),
+        (34867, Tag::ISOSpeed),
+        (34868, Tag::ISOSpeedLatitudeyyy),
+        (34869, Tag::ISOSpeedLatitudezzz),
+        (36864, Tag::ExifVersion),
+        (36867, Tag::DateTimeOriginal),
+        (36868, Tag::DateTimeDigitized),
+        (36880, Tag::OffsetTime),
+        (36881, Tag::OffsetTimeOriginal),
+        (36882, Tag::OffsetTimeDigitized),
+        (37121, Tag::ComponentsConfiguration),
+        (37122, Tag::CompressedBitsPerPixel),
+        (37377, Tag::ShutterSpeedValue),
+        (37378, Tag::ApertureValue),
+        (37379, Tag::BrightnessValue),
+        (37380, Tag::ExposureBiasValue),
+        (37381, Tag::MaxApertureValue),
+        (37382, Tag::SubjectDistance),
+        (37383, Tag::MeteringMode),
+        (37384, Tag::LightSource),
+        (37385, Tag::Flash),
+        (37386, Tag::FocalLength),
+        (37393, Tag::ImageNumber),
+        (37396, Tag::SubjectArea),
+        (37500, Tag::MakerNote),
+        (37510, Tag::UserComment),
+        (37520, Tag::SubSecTime),
+        (37521, Tag::SubSecTimeOriginal),
+        (37522, Tag::SubSecTimeDigitized),
+        (37888, Tag::Temperature),
+        (37889, Tag::Humidity),
+        (37890, Tag::Pressure),
+        (37891, Tag::WaterDepth),
+        (37892, Tag::Acceleration),
+        (37893, Tag::CameraElevationAngle),
+        (40960, Tag::FlashpixVersion),
+        (40961, Tag::ColorSpace),
+        (40962, Tag::PixelXDimension),
+        (40963, Tag::PixelYDimension),
+        (40964, Tag::RelatedSoundFile),
+        (40965, Tag::InteroperabilityIFD),
+        (41483, Tag::FlashEnergy),
+        (41484, Tag::SpatialFrequencyResponse),
+        (41486, Tag::FocalPlaneXResolution),
+        (41487, Tag::FocalPlaneYResolution),
+        (41488, Tag::FocalPlaneResolutionUnit),
+        (41492, Tag::SubjectLocation),
+        (41493, Tag::ExposureIndex),
+        (41495, Tag::SensingMethod),
+        (41728, Tag::FileSource),
+        (41729, Tag::SceneType),
+        (41730, Tag::ExifCFAPattern),
+        (41985, Tag::CustomRendered),
+        (41986, Tag::ExposureMode),
+        (41987, Tag::WhiteBalance),
+        (41988, Tag::DigitalZoomRatio),
+        (41989, Tag::FocalLengthIn35mmFilm),
+        (41990, Tag::SceneCaptureType),
+        (41991, Tag::GainControl),
+        (41992, Tag::Contrast),
+        (41993, Tag::Saturation),
+        (41994, Tag::Sharpness),
+        (41995, Tag::DeviceSettingDescription),
+        (41996, Tag::SubjectDistanceRange),
+        (42016, Tag::ImageUniqueID),
+        (42032, Tag::CameraOwnerName),
+        (42033, Tag::BodySerialNumber),
+        (42034, Tag::LensSpecification),
+        (42035, Tag::LensMake),
+        (42036, Tag::LensModel),
+        (42037, Tag::LensSerialNumber),
+        (42240, Tag::Gamma),
+        // Digital Negative Specification, Version 1.4.0.0, page 22
+        (50706, Tag::DNGVersion),
+        // Digital Negative Specification, Version 1.4.0.0, page 22
+        (50707, Tag::DNGBackwardVersion),
+        // Digital Negative Specification, Version 1.4.0.0, page 23
+        (50708, Tag::UniqueCameraModel),
+        (50709, Tag::LocalizedCameraModel),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50710, Tag::CFAPlaneColor),
+        (50711, Tag::CFALayout),
+        (50712, Tag::LinearizationTable),
+        (50713, Tag::BlackLevelRepeatDim),
+        // Digital Negative Specification, Version 1.4.0.0,  page 27
+        // See chapter 5, “Mapping Raw Values to Linear Reference Values” on page 77 for
+        // details of the processing model.
+        (50714, Tag::BlackLevel),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50715, Tag::BlackLevelDeltaH),
+        (50716, Tag::BlackLevelDeltaV),
+        // Digital Negative Specification, Version 1.4.0.0,  page 29
+        // See chapter 5, “Mapping Raw Values to Linear Reference Values” on page 77 for
+        // details of the processing model.
+        (50717, Tag::WhiteLevel),
+        // Digital Negative Specification, Version 1.4.0.0,  page 29
+        (50718, Tag::DefaultScale),
+        // Digital Negative Specification, Version 1.4.0.0,  page 30
+        (50719, Tag::DefaultCropOrigin),
+        // Digital Negative Specification, Version 1.4.0.0,  page 31
+        // http://www.barrypearson.co.uk/articles/dng/specification.htm
+        (50720, Tag::DefaultCropSize),
+        // Digital Negative Specification, Version 1.4.0.0, page 32
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        (50721, Tag::ColorMatrix1),
+        // Digital Negative Specification, Version 1.4.0.0, page 33
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        (50722, Tag::ColorMatrix2),
+        // Digital Negative Specification, Version 1.4.0.0, page 34
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        (50723, Tag::CameraCalibration1),
+        // Digital Negative Specification, Version 1.4.0.0, page 34
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        (50724, Tag::CameraCalibration2),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50725, Tag::ReductionMatrix1),
+        (50726, Tag::ReductionMatrix2),
+        // Digital Negative Specification, Version 1.4.0.0, page 36
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        (50727, Tag::AnalogBalance),
+        // Digital Negative Specification, Version 1.4.0.0, page 37
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        (50728, Tag::AsShotNeutral),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50729, Tag::AsShotWhiteXY),
+        // Digital Negative Specification, Version 1.4.0.0, page 38
+        (50730, Tag::BaselineExposure),
+        // Digital Negative Specification, Version 1.4.0.0, page 38
+        (50731, Tag::BaselineNoise),
+        // Digital Negative Specification, Version 1.4.0.0, page 39
+        (50732, Tag::BaselineSharpness),
+        (50733, Tag::BayerGreenSplit),
+        // Digital Negative Specification, Version 1.4.0.0, page 40
+        (50734, Tag::LinearResponseLimit),
+        (50735, Tag::CameraSerialNumber),
+        (50736, Tag::LensInfo),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50737, Tag::ChromaBlurRadius),
+        // Digital Negative Specification, Version 1.4.0.0, page 42
+        (50738, Tag::AntiAliasStrength),
+        // Digital Negative Specification, Version 1.4.0.0, page 42
+        (50739, Tag::ShadowScale),
+        // Digital Negative Specification, Version 1.4.0.0, page 43
+        (50740, Tag::DNGPrivateData),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50741, Tag::MakerNoteSafety),
+        // Digital Negative Specification, Version 1.4.0.0, page 31
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        //
+        // Exif, page 55
+        (50778, Tag::CalibrationIlluminant1),
+        // Digital Negative Specification, Version 1.4.0.0, page 32
+        //
+        // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
+        // of the color-processing model.
+        //
+        // Exif, page 55
+        (50779, Tag::CalibrationIlluminant2),
+        // Digital Negative Specification, Version 1.4.0.0,  page 30
+        (50780, Tag::BestQualityScale),
+        // Digital Negative Specification, Version 1.4.0.0, page 45
+        (50781, Tag::RawDataUniqueID),
+        (50827, Tag::OriginalRawFileName),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50828, Tag::OriginalRawFileData),
+        (50829, Tag::ActiveArea),
+        (50830, Tag::MaskedAreas),
+        (50831, Tag::AsShotICCProfile),
+        (50832, Tag::AsShotPreProfileMatrix),
+        (50833, Tag::CurrentICCProfile),
+        (50834, Tag::CurrentPreProfileMatrix),
+        (50879, Tag::ColorimetricReference),
+        (50931, Tag::CameraCalibrationSignature),
+        (50932, Tag::ProfileCalibrationSignature),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50933, Tag::ExtraCameraProfiles),
+        (50934, Tag::AsShotProfileName),
+        (50935, Tag::NoiseReductionApplied),
+        (50936, Tag::ProfileName),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50937, Tag::ProfileHueSatMapDims),
+        (50938, Tag::ProfileHueSatMapData1),
+        (50939, Tag::ProfileHueSatMapData2),
+        (50940, Tag::ProfileToneCurve),
+        (50941, Tag::ProfileEmbedPolicy),
+        (50942, Tag::ProfileCopyright),
+        // Digital Negative Specification, Version 1.4.0.0, page 58
+        // Application is described in detail in Chapter 6.
+        (50964, Tag::ForwardMatrix1),
+        // Digital Negative Specification, Version 1.4.0.0, page 59
+        // Application is described in detail in Chapter 6.
+        (50965, Tag::ForwardMatrix2),
+        (50966, Tag::PreviewApplicationName),
+        (50967, Tag::PreviewApplicationVersion),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50968, Tag::PreviewSettingsName),
+        // Digital Negative Specification, Version 1.4.0.0, page 61
+        (50969, Tag::PreviewSettingsDigest),
+        // Digital Negative Specification, Version 1.4.0.0, page 61
+        (50970, Tag::PreviewColorSpace),
+        (50971, Tag::PreviewDateTime),
+        (50972, Tag::RawImageDigest),
+        // Digital Negative Specification, Version 1.7.1.0
+        (50973, Tag::OriginalRawFileDigest),
+        (50974, Tag::SubTileBlockSize),
+        (50975, Tag::RowInterleaveFactor),
+        (50981, Tag::ProfileLookTableDims),
+        (50982, Tag::ProfileLookTableData),
+        (51008, Tag::OpcodeList1),
+        (51009, Tag::OpcodeList2),
+        (51022, Tag::OpcodeList3),
+        // Digital Negative Specification, Version 1.4.0.0, page 67
+        (51041, Tag::NoiseProfile),
+        // Digital Negative Specification, Version 1.7.1.0
+        (51089, Tag::OriginalDefaultFinalSize),
+        (51090, Tag::OriginalBestQualityFinalSize),
+        (51091, Tag::OriginalDefaultCropSize),
+        (51107, Tag::ProfileHueSatMapEncoding),
+        (51108, Tag::ProfileLookTableEncoding),
+        (51109, Tag::BaselineExposureOffset),
+        (51110, Tag::DefaultBlackRender),
+        (51111, Tag::NewRawImageDigest),
+        (51112, Tag::RawToPreviewGain),
+        (51125, Tag::DefaultUserCrop),
+        (51177, Tag::DepthFormat),
+        (51178, Tag::DepthNear),
+        (51179, Tag::DepthFar),
+        (51180, Tag::DepthUnits),
+        (51181, Tag::DepthMeasureType),
+        (51182, Tag::EnhanceParams),
+        (52525, Tag::ProfileGainTableMap),
+        (52526, Tag::SemanticName),
+        (52528, Tag::SemanticInstanceID),
+        (52529, Tag::CalibrationIlluminant3),
+        (52530, Tag::CameraCalibration3),
+        (52531, Tag::ColorMatrix3),
+        (52532, Tag::ForwardMatrix3),
+        (52533, Tag::IlluminantData1),
+        (52534, Tag::IlluminantData2),
+        (52535, Tag::IlluminantData3),
+        (52536, Tag::MaskSubArea),
+        (52537, Tag::ProfileHueSatMapData3),
+        (52538, Tag::ReductionMatrix3),
+        (52543, Tag::RGBTables),
+        (52544, Tag::ProfileGainTableMap2),
+        (52547, Tag::ColumnInterleaveFactor),
+        (52548, Tag::ImageSequenceInfo),
+        (52550, Tag::ImageStats),
+        (52551, Tag::ProfileDynamicRange),
+        (52552, Tag::ProfileGroupName),
+        (52553, Tag::JXLDistance),
+        (52554, Tag::JXLEffort),
+        (52555, Tag::JXLDecodeSpeed),
+    ]
+
+    // Tags in the GPS IFD have their own numbering, which overlaps that of TIFF tags, so they must
+    // be told apart by the IFD they come from.
+    GPS_TAGS = 1 [
+        (0, Tag::GPSVersionID),
+        (1, Tag::GPSLatitudeRef),
+        (2, Tag::GPSLatitude),
+        (3, Tag::GPSLongitudeRef),
+        (4, Tag::GPSLongitude),
+        (5, Tag::GPSAltitudeRef),
+        (6, Tag::GPSAltitude),
+        (7, Tag::GPSTimeStamp),
+        (8, Tag::GPSSatellites),
+        (9, Tag::GPSStatus),
+        (10, Tag::GPSMeasureMode),
+        (11, Tag::GPSDOP),
+        (12, Tag::GPSSpeedRef),
+        (13, Tag::GPSSpeed),
+        (14, Tag::GPSTrackRef),
+        (15, Tag::GPSTrack),
+        (16, Tag::GPSImgDirectionRef),
+        (17, Tag::GPSImgDirection),
+        (18, Tag::GPSMapDatum),
+        (19, Tag::GPSDestLatitudeRef),
+        (20, Tag::GPSDestLatitude),
+        (21, Tag::GPSDestLongitudeRef),
+        (22, Tag::GPSDestLongitude),
+        (23, Tag::GPSDestBearingRef),
+        (24, Tag::GPSDestBearing),
+        (25, Tag::GPSDestDistanceRef),
+        (26, Tag::GPSDestDistance),
+        (27, Tag::GPSProcessingMethod),
+        (28, Tag::GPSAreaInformation),
+        (29, Tag::GPSDateStamp),
+        (30, Tag::GPSDifferential),
+        (31, Tag::GPSHPositioningError),
+    ]
+
+    // Same as GPS tags, Interoperability tags have their own numbering
+    INTEROPERABILITY_TAGS = 2 [
+        (1, Tag::InteroperabilityIndex),
+        (2, Tag::InteroperabilityVersion),
+        (4096, Tag::RelatedImageFileFormat),
+        (4097, Tag::RelatedImageWidth),
+        (4098, Tag::RelatedImageLength),
+    ]
+}
 
-            // Digital Negative Specification, Version 1.4.0.0, page 43
-            50740 => Tag::DNGPrivateData,
+// Prefixes telling tag numbers of GPS and Interoperability IFDs from IFD0 ones in text
+const GPS_NAMESPACE: &str = "GPS:";
+const INTEROPERABILITY_NAMESPACE: &str = "Interoperability:";
 
-            // Digital Negative Specification, Version 1.4.0.0, page 31
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            //
-            // Exif, page 55
-            50778 => Tag::CalibrationIlluminant1,
+impl Tag {
+    #[must_use]
+    pub const fn new(tag: u16) -> Tag {
+        find(TAGS, tag)
+    }
 
-            // Digital Negative Specification, Version 1.4.0.0, page 32
-            //
-            // See chapter 6, “Mapping Camera Color Space to CIE XYZ Space” on page 79 for details
-            // of the color-processing model.
-            //
-            // Exif, page 55
-            50779 => Tag::CalibrationIlluminant2,
+    #[must_use]
+    pub const fn new_gps(tag: u16) -> Tag {
+        find(GPS_TAGS, tag)
+    }
 
-            // Digital Negative Specification, Version 1.4.0.0,  page 30
-            50780 => Tag::BestQualityScale,
+    #[must_use]
+    pub const fn new_interoperability(tag: u16) -> Tag {
+        find(INTEROPERABILITY_TAGS, tag)
+    }
+}
 
-            // Digital Negative Specification, Version 1.4.0.0, page 45
-            50781 => Tag::RawDataUniqueID,
+// Binary search, as tables are sorted by number
+const fn find(tags: &[(u16, Tag)], tag: u16) -> Tag {
+    let mut low: usize = 0;
+    let mut high: usize = tags.len();
+    while low < high {
+        let middle: usize = low + (high - low) / 2;
+        if tags[middle].0 == tag {
+            return tags[middle].1;
+        }
+        if tags[middle].0 < tag {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    Tag::Other(tag)
+}
 
-            50827 => Tag::OriginalRawFileName,
+// Tags are ordered by their numeric value, the same order TIFF 6.0 requires entries in an IFD to be
+// sorted in, so ordered maps iterate fields the way they are laid out in the file. Other(n) is
+// not equal to the tag numbered n, so it comes right after it rather than comparing equal.
+impl Ord for Tag {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |tag: &Tag| (tag.id(), tag.namespace(), matches!(tag, Tag::Other(_)));
+        key(self).cmp(&key(other))
+    }
+}
 
-            50931 => Tag::CameraCalibrationSignature,
+impl PartialOrd for Tag {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-            50932 => Tag::ProfileCalibrationSignature,
+/*************************************************************************************************
+ *                                          Tag registry                                         *
+ *************************************************************************************************/
 
-            50936 => Tag::ProfileName,
+// How many values a field has, as the specs define it for its tag
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValueCount {
+    Fixed(u32),
+    // One per sample, SamplesPerPixel of them
+    PerSample,
+    // Depends on the image, such as one per strip or per color plane, or arbitrary, as text
+    Variable,
+}
 
-            50941 => Tag::ProfileEmbedPolicy,
+// What the specs say about a tag, for consumers to validate, print or write fields without
+// hard-coding the TIFF, DNG and Exif specifications themselves
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TagInfo {
+    pub name: &'static str,
+    // Types the field may have, the first one being the one the specs prefer
+    pub types: &'static [u16],
+    pub count: ValueCount,
+}
 
-            50942 => Tag::ProfileCopyright,
+impl TagInfo {
+    #[must_use]
+    pub fn allows_type(&self, type_: u16) -> bool {
+        self.types.contains(&type_)
+    }
+}
 
-            // Digital Negative Specification, Version 1.4.0.0, page 58
-            // Application is described in detail in Chapter 6.
-            50964 => Tag::ForwardMatrix1,
+// Exif 3.0 allows UTF-8 wherever ASCII was
+const TEXT: &[u16] = &[ASCII, UTF8];
+const TEXT_OR_BYTES: &[u16] = &[ASCII, UTF8, BYTE];
+const BYTE_ONLY: &[u16] = &[BYTE];
+// XMP is BYTE per DNG, but UNDEFINED is common in the wild
+const BYTES: &[u16] = &[BYTE, UNDEFINED];
+// IPTC is most often written as LONG, for no good reason
+const BYTES_OR_LONG: &[u16] = &[BYTE, UNDEFINED, LONG];
+const UNDEFINED_ONLY: &[u16] = &[UNDEFINED];
+const SHORT_ONLY: &[u16] = &[SHORT];
+const LONG_ONLY: &[u16] = &[LONG];
+const IFD_OFFSETS: &[u16] = &[LONG, IFD];
+const SHORT_OR_LONG: &[u16] = &[SHORT, LONG];
+const SHORT_OR_RATIONAL: &[u16] = &[SHORT, RATIONAL];
+const SHORT_LONG_OR_RATIONAL: &[u16] = &[SHORT, LONG, RATIONAL];
+const RATIONAL_ONLY: &[u16] = &[RATIONAL];
+const SRATIONAL_ONLY: &[u16] = &[SRATIONAL];
+const FLOAT_ONLY: &[u16] = &[FLOAT];
+const DOUBLE_ONLY: &[u16] = &[DOUBLE];
+
+// Every tag this crate knows about, in the same order as the Tag enum
+const REGISTRY: &[(Tag, TagInfo)] = &[
+    (
+        Tag::NewSubFileType,
+        info("NewSubFileType", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ImageWidth,
+        info("ImageWidth", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ImageLength,
+        info("ImageLength", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::BitsPerSample,
+        info("BitsPerSample", SHORT_ONLY, ValueCount::PerSample),
+    ),
+    (
+        Tag::Compression,
+        info("Compression", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::PhotometricInterpretation,
+        info(
+            "PhotometricInterpretation",
+            SHORT_ONLY,
+            ValueCount::Fixed(1),
+        ),
+    ),
+    (
+        Tag::FillOrder,
+        info("FillOrder", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ImageDescription,
+        info("ImageDescription", TEXT, ValueCount::Variable),
+    ),
+    (Tag::Make, info("Make", TEXT, ValueCount::Variable)),
+    (Tag::Model, info("Model", TEXT, ValueCount::Variable)),
+    (
+        Tag::StripOffsets,
+        info("StripOffsets", SHORT_OR_LONG, ValueCount::Variable),
+    ),
+    (
+        Tag::Orientation,
+        info("Orientation", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SamplesPerPixel,
+        info("SamplesPerPixel", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::RowsPerStrip,
+        info("RowsPerStrip", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::StripByteCounts,
+        info("StripByteCounts", SHORT_OR_LONG, ValueCount::Variable),
+    ),
+    (
+        Tag::XResolution,
+        info("XResolution", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::YResolution,
+        info("YResolution", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::PlanarConfiguration,
+        info("PlanarConfiguration", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ResolutionUnit,
+        info("ResolutionUnit", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::TransferFunction,
+        info("TransferFunction", SHORT_ONLY, ValueCount::Fixed(768)),
+    ),
+    (Tag::Software, info("Software", TEXT, ValueCount::Variable)),
+    (Tag::DateTime, info("DateTime", TEXT, ValueCount::Fixed(20))),
+    (Tag::Artist, info("Artist", TEXT, ValueCount::Variable)),
+    (
+        Tag::WhitePoint,
+        info("WhitePoint", RATIONAL_ONLY, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::PrimaryChromaticities,
+        info("PrimaryChromaticities", RATIONAL_ONLY, ValueCount::Fixed(6)),
+    ),
+    (
+        Tag::TileWidth,
+        info("TileWidth", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::TileLength,
+        info("TileLength", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::TileOffsets,
+        info("TileOffsets", LONG_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::TileByteCounts,
+        info("TileByteCounts", SHORT_OR_LONG, ValueCount::Variable),
+    ),
+    (
+        Tag::SubIFDs,
+        info("SubIFDs", IFD_OFFSETS, ValueCount::Variable),
+    ),
+    (
+        Tag::JPEGInterchangeFormat,
+        info("JPEGInterchangeFormat", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::JPEGInterchangeFormatLength,
+        info(
+            "JPEGInterchangeFormatLength",
+            LONG_ONLY,
+            ValueCount::Fixed(1),
+        ),
+    ),
+    (
+        Tag::YCbCrCoefficients,
+        info("YCbCrCoefficients", RATIONAL_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::YCbCrSubSampling,
+        info("YCbCrSubSampling", SHORT_ONLY, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::YCbCrPositioning,
+        info("YCbCrPositioning", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ReferenceBlackWhite,
+        info("ReferenceBlackWhite", RATIONAL_ONLY, ValueCount::Fixed(6)),
+    ),
+    (Tag::XMP, info("XMP", BYTES, ValueCount::Variable)),
+    (
+        Tag::CFARepeatPatternDim,
+        info("CFARepeatPatternDim", SHORT_ONLY, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::CFAPattern,
+        info("CFAPattern", BYTE_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::Copyright,
+        info("Copyright", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::ExposureTime,
+        info("ExposureTime", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::FNumber,
+        info("FNumber", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::IPTCNAA,
+        info("IPTCNAA", BYTES_OR_LONG, ValueCount::Variable),
+    ),
+    (
+        Tag::Photoshop,
+        info("Photoshop", BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::ExifIFD,
+        info("ExifIFD", IFD_OFFSETS, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::InterColorProfile,
+        info("InterColorProfile", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ExposureProgram,
+        info("ExposureProgram", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SpectralSensitivity,
+        info("SpectralSensitivity", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::GPSInfo,
+        info("GPSInfo", IFD_OFFSETS, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ISOSpeedRatings,
+        info("ISOSpeedRatings", SHORT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::OECF,
+        info("OECF", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::SensitivityType,
+        info("SensitivityType", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::StandardOutputSensitivity,
+        info("StandardOutputSensitivity", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::RecommendedExposureIndex,
+        info("RecommendedExposureIndex", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ISOSpeed,
+        info("ISOSpeed", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ISOSpeedLatitudeyyy,
+        info("ISOSpeedLatitudeyyy", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ISOSpeedLatitudezzz,
+        info("ISOSpeedLatitudezzz", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ExifVersion,
+        info("ExifVersion", UNDEFINED_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::DateTimeOriginal,
+        info("DateTimeOriginal", TEXT, ValueCount::Fixed(20)),
+    ),
+    (
+        Tag::DateTimeDigitized,
+        info("DateTimeDigitized", TEXT, ValueCount::Fixed(20)),
+    ),
+    (
+        Tag::OffsetTime,
+        info("OffsetTime", TEXT, ValueCount::Fixed(7)),
+    ),
+    (
+        Tag::OffsetTimeOriginal,
+        info("OffsetTimeOriginal", TEXT, ValueCount::Fixed(7)),
+    ),
+    (
+        Tag::OffsetTimeDigitized,
+        info("OffsetTimeDigitized", TEXT, ValueCount::Fixed(7)),
+    ),
+    (
+        Tag::ComponentsConfiguration,
+        info(
+            "ComponentsConfiguration",
+            UNDEFINED_ONLY,
+            ValueCount::Fixed(4),
+        ),
+    ),
+    (
+        Tag::CompressedBitsPerPixel,
+        info(
+            "CompressedBitsPerPixel",
+            RATIONAL_ONLY,
+            ValueCount::Fixed(1),
+        ),
+    ),
+    (
+        Tag::ShutterSpeedValue,
+        info("ShutterSpeedValue", SRATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ApertureValue,
+        info("ApertureValue", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::BrightnessValue,
+        info("BrightnessValue", SRATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ExposureBiasValue,
+        info("ExposureBiasValue", SRATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::MaxApertureValue,
+        info("MaxApertureValue", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SubjectDistance,
+        info("SubjectDistance", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::MeteringMode,
+        info("MeteringMode", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::LightSource,
+        info("LightSource", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (Tag::Flash, info("Flash", SHORT_ONLY, ValueCount::Fixed(1))),
+    (
+        Tag::FocalLength,
+        info("FocalLength", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ImageNumber,
+        info("ImageNumber", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SubjectArea,
+        info("SubjectArea", SHORT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::MakerNote,
+        info("MakerNote", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::UserComment,
+        info("UserComment", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::SubSecTime,
+        info("SubSecTime", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::SubSecTimeOriginal,
+        info("SubSecTimeOriginal", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::SubSecTimeDigitized,
+        info("SubSecTimeDigitized", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::Temperature,
+        info("Temperature", SRATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::Humidity,
+        info("Humidity", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::Pressure,
+        info("Pressure", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::WaterDepth,
+        info("WaterDepth", SRATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::Acceleration,
+        info("Acceleration", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::CameraElevationAngle,
+        info("CameraElevationAngle", SRATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::FlashpixVersion,
+        info("FlashpixVersion", UNDEFINED_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::ColorSpace,
+        info("ColorSpace", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::PixelXDimension,
+        info("PixelXDimension", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::PixelYDimension,
+        info("PixelYDimension", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::RelatedSoundFile,
+        info("RelatedSoundFile", TEXT, ValueCount::Fixed(13)),
+    ),
+    (
+        Tag::InteroperabilityIFD,
+        info("InteroperabilityIFD", IFD_OFFSETS, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::FlashEnergy,
+        info("FlashEnergy", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SpatialFrequencyResponse,
+        info(
+            "SpatialFrequencyResponse",
+            UNDEFINED_ONLY,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::FocalPlaneXResolution,
+        info("FocalPlaneXResolution", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::FocalPlaneYResolution,
+        info("FocalPlaneYResolution", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::FocalPlaneResolutionUnit,
+        info("FocalPlaneResolutionUnit", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SubjectLocation,
+        info("SubjectLocation", SHORT_ONLY, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::ExposureIndex,
+        info("ExposureIndex", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SensingMethod,
+        info("SensingMethod", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::FileSource,
+        info("FileSource", UNDEFINED_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SceneType,
+        info("SceneType", UNDEFINED_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ExifCFAPattern,
+        info("ExifCFAPattern", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::CustomRendered,
+        info("CustomRendered", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ExposureMode,
+        info("ExposureMode", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::WhiteBalance,
+        info("WhiteBalance", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DigitalZoomRatio,
+        info("DigitalZoomRatio", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::FocalLengthIn35mmFilm,
+        info("FocalLengthIn35mmFilm", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::SceneCaptureType,
+        info("SceneCaptureType", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GainControl,
+        info("GainControl", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::Contrast,
+        info("Contrast", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::Saturation,
+        info("Saturation", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::Sharpness,
+        info("Sharpness", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DeviceSettingDescription,
+        info(
+            "DeviceSettingDescription",
+            UNDEFINED_ONLY,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::SubjectDistanceRange,
+        info("SubjectDistanceRange", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ImageUniqueID,
+        info("ImageUniqueID", TEXT, ValueCount::Fixed(33)),
+    ),
+    (
+        Tag::CameraOwnerName,
+        info("CameraOwnerName", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::BodySerialNumber,
+        info("BodySerialNumber", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::LensSpecification,
+        info("LensSpecification", RATIONAL_ONLY, ValueCount::Fixed(4)),
+    ),
+    (Tag::LensMake, info("LensMake", TEXT, ValueCount::Variable)),
+    (
+        Tag::LensModel,
+        info("LensModel", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::LensSerialNumber,
+        info("LensSerialNumber", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::Gamma,
+        info("Gamma", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DNGVersion,
+        info("DNGVersion", BYTE_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::DNGBackwardVersion,
+        info("DNGBackwardVersion", BYTE_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::UniqueCameraModel,
+        info("UniqueCameraModel", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::LocalizedCameraModel,
+        info("LocalizedCameraModel", TEXT_OR_BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::CFAPlaneColor,
+        info("CFAPlaneColor", BYTE_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::CFALayout,
+        info("CFALayout", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::LinearizationTable,
+        info("LinearizationTable", SHORT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::BlackLevelRepeatDim,
+        info("BlackLevelRepeatDim", SHORT_ONLY, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::BlackLevel,
+        info("BlackLevel", SHORT_LONG_OR_RATIONAL, ValueCount::Variable),
+    ),
+    (
+        Tag::BlackLevelDeltaH,
+        info("BlackLevelDeltaH", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::BlackLevelDeltaV,
+        info("BlackLevelDeltaV", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::WhiteLevel,
+        info("WhiteLevel", SHORT_OR_LONG, ValueCount::PerSample),
+    ),
+    (
+        Tag::DefaultScale,
+        info("DefaultScale", RATIONAL_ONLY, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::DefaultCropOrigin,
+        info(
+            "DefaultCropOrigin",
+            SHORT_LONG_OR_RATIONAL,
+            ValueCount::Fixed(2),
+        ),
+    ),
+    (
+        Tag::DefaultCropSize,
+        info(
+            "DefaultCropSize",
+            SHORT_LONG_OR_RATIONAL,
+            ValueCount::Fixed(2),
+        ),
+    ),
+    (
+        Tag::ColorMatrix1,
+        info("ColorMatrix1", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ColorMatrix2,
+        info("ColorMatrix2", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::CameraCalibration1,
+        info("CameraCalibration1", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::CameraCalibration2,
+        info("CameraCalibration2", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ReductionMatrix1,
+        info("ReductionMatrix1", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ReductionMatrix2,
+        info("ReductionMatrix2", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::AnalogBalance,
+        info("AnalogBalance", RATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::AsShotNeutral,
+        info("AsShotNeutral", SHORT_OR_RATIONAL, ValueCount::Variable),
+    ),
+    (
+        Tag::AsShotWhiteXY,
+        info("AsShotWhiteXY", RATIONAL_ONLY, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::BaselineExposure,
+        info("BaselineExposure", SRATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::BaselineNoise,
+        info("BaselineNoise", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::BaselineSharpness,
+        info("BaselineSharpness", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::BayerGreenSplit,
+        info("BayerGreenSplit", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::LinearResponseLimit,
+        info("LinearResponseLimit", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::CameraSerialNumber,
+        info("CameraSerialNumber", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::LensInfo,
+        info("LensInfo", RATIONAL_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::ChromaBlurRadius,
+        info("ChromaBlurRadius", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::AntiAliasStrength,
+        info("AntiAliasStrength", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ShadowScale,
+        info("ShadowScale", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DNGPrivateData,
+        info("DNGPrivateData", BYTE_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::MakerNoteSafety,
+        info("MakerNoteSafety", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::CalibrationIlluminant1,
+        info("CalibrationIlluminant1", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::CalibrationIlluminant2,
+        info("CalibrationIlluminant2", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::BestQualityScale,
+        info("BestQualityScale", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::RawDataUniqueID,
+        info("RawDataUniqueID", BYTE_ONLY, ValueCount::Fixed(16)),
+    ),
+    (
+        Tag::OriginalRawFileName,
+        info("OriginalRawFileName", TEXT_OR_BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::OriginalRawFileData,
+        info("OriginalRawFileData", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ActiveArea,
+        info("ActiveArea", SHORT_OR_LONG, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::MaskedAreas,
+        info("MaskedAreas", SHORT_OR_LONG, ValueCount::Variable),
+    ),
+    (
+        Tag::AsShotICCProfile,
+        info("AsShotICCProfile", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::AsShotPreProfileMatrix,
+        info(
+            "AsShotPreProfileMatrix",
+            SRATIONAL_ONLY,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::CurrentICCProfile,
+        info("CurrentICCProfile", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::CurrentPreProfileMatrix,
+        info(
+            "CurrentPreProfileMatrix",
+            SRATIONAL_ONLY,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::ColorimetricReference,
+        info("ColorimetricReference", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::CameraCalibrationSignature,
+        info(
+            "CameraCalibrationSignature",
+            TEXT_OR_BYTES,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::ProfileCalibrationSignature,
+        info(
+            "ProfileCalibrationSignature",
+            TEXT_OR_BYTES,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::ExtraCameraProfiles,
+        info("ExtraCameraProfiles", LONG_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::AsShotProfileName,
+        info("AsShotProfileName", TEXT_OR_BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::NoiseReductionApplied,
+        info("NoiseReductionApplied", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ProfileName,
+        info("ProfileName", TEXT_OR_BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileHueSatMapDims,
+        info("ProfileHueSatMapDims", LONG_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::ProfileHueSatMapData1,
+        info("ProfileHueSatMapData1", FLOAT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileHueSatMapData2,
+        info("ProfileHueSatMapData2", FLOAT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileToneCurve,
+        info("ProfileToneCurve", FLOAT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileEmbedPolicy,
+        info("ProfileEmbedPolicy", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ProfileCopyright,
+        info("ProfileCopyright", TEXT_OR_BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::ForwardMatrix1,
+        info("ForwardMatrix1", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ForwardMatrix2,
+        info("ForwardMatrix2", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::PreviewApplicationName,
+        info(
+            "PreviewApplicationName",
+            TEXT_OR_BYTES,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::PreviewApplicationVersion,
+        info(
+            "PreviewApplicationVersion",
+            TEXT_OR_BYTES,
+            ValueCount::Variable,
+        ),
+    ),
+    (
+        Tag::PreviewSettingsName,
+        info("PreviewSettingsName", TEXT_OR_BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::PreviewSettingsDigest,
+        info("PreviewSettingsDigest", BYTE_ONLY, ValueCount::Fixed(16)),
+    ),
+    (
+        Tag::PreviewColorSpace,
+        info("PreviewColorSpace", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::PreviewDateTime,
+        info("PreviewDateTime", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::RawImageDigest,
+        info("RawImageDigest", BYTE_ONLY, ValueCount::Fixed(16)),
+    ),
+    (
+        Tag::OriginalRawFileDigest,
+        info("OriginalRawFileDigest", BYTE_ONLY, ValueCount::Fixed(16)),
+    ),
+    (
+        Tag::SubTileBlockSize,
+        info("SubTileBlockSize", SHORT_OR_LONG, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::RowInterleaveFactor,
+        info("RowInterleaveFactor", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ProfileLookTableDims,
+        info("ProfileLookTableDims", LONG_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::ProfileLookTableData,
+        info("ProfileLookTableData", FLOAT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::OpcodeList1,
+        info("OpcodeList1", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::OpcodeList2,
+        info("OpcodeList2", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::OpcodeList3,
+        info("OpcodeList3", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::NoiseProfile,
+        info("NoiseProfile", DOUBLE_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::OriginalDefaultFinalSize,
+        info(
+            "OriginalDefaultFinalSize",
+            SHORT_OR_LONG,
+            ValueCount::Fixed(2),
+        ),
+    ),
+    (
+        Tag::OriginalBestQualityFinalSize,
+        info(
+            "OriginalBestQualityFinalSize",
+            SHORT_OR_LONG,
+            ValueCount::Fixed(2),
+        ),
+    ),
+    (
+        Tag::OriginalDefaultCropSize,
+        info(
+            "OriginalDefaultCropSize",
+            SHORT_LONG_OR_RATIONAL,
+            ValueCount::Fixed(2),
+        ),
+    ),
+    (
+        Tag::ProfileHueSatMapEncoding,
+        info("ProfileHueSatMapEncoding", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::ProfileLookTableEncoding,
+        info("ProfileLookTableEncoding", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::BaselineExposureOffset,
+        info(
+            "BaselineExposureOffset",
+            SRATIONAL_ONLY,
+            ValueCount::Fixed(1),
+        ),
+    ),
+    (
+        Tag::DefaultBlackRender,
+        info("DefaultBlackRender", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::NewRawImageDigest,
+        info("NewRawImageDigest", BYTE_ONLY, ValueCount::Fixed(16)),
+    ),
+    (
+        Tag::RawToPreviewGain,
+        info("RawToPreviewGain", DOUBLE_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DefaultUserCrop,
+        info("DefaultUserCrop", RATIONAL_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::DepthFormat,
+        info("DepthFormat", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DepthNear,
+        info("DepthNear", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DepthFar,
+        info("DepthFar", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DepthUnits,
+        info("DepthUnits", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::DepthMeasureType,
+        info("DepthMeasureType", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::EnhanceParams,
+        info("EnhanceParams", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileGainTableMap,
+        info("ProfileGainTableMap", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::SemanticName,
+        info("SemanticName", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::SemanticInstanceID,
+        info("SemanticInstanceID", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::CalibrationIlluminant3,
+        info("CalibrationIlluminant3", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::CameraCalibration3,
+        info("CameraCalibration3", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ColorMatrix3,
+        info("ColorMatrix3", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ForwardMatrix3,
+        info("ForwardMatrix3", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::IlluminantData1,
+        info("IlluminantData1", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::IlluminantData2,
+        info("IlluminantData2", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::IlluminantData3,
+        info("IlluminantData3", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::MaskSubArea,
+        info("MaskSubArea", LONG_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::ProfileHueSatMapData3,
+        info("ProfileHueSatMapData3", FLOAT_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ReductionMatrix3,
+        info("ReductionMatrix3", SRATIONAL_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::RGBTables,
+        info("RGBTables", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileGainTableMap2,
+        info("ProfileGainTableMap2", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ColumnInterleaveFactor,
+        info(
+            "ColumnInterleaveFactor",
+            SHORT_OR_LONG,
+            ValueCount::Fixed(1),
+        ),
+    ),
+    (
+        Tag::ImageSequenceInfo,
+        info("ImageSequenceInfo", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ImageStats,
+        info("ImageStats", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileDynamicRange,
+        info("ProfileDynamicRange", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::ProfileGroupName,
+        info("ProfileGroupName", TEXT_OR_BYTES, ValueCount::Variable),
+    ),
+    (
+        Tag::JXLDistance,
+        info("JXLDistance", FLOAT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::JXLEffort,
+        info("JXLEffort", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::JXLDecodeSpeed,
+        info("JXLDecodeSpeed", LONG_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSVersionID,
+        info("GPSVersionID", BYTE_ONLY, ValueCount::Fixed(4)),
+    ),
+    (
+        Tag::GPSLatitudeRef,
+        info("GPSLatitudeRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSLatitude,
+        info("GPSLatitude", RATIONAL_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::GPSLongitudeRef,
+        info("GPSLongitudeRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSLongitude,
+        info("GPSLongitude", RATIONAL_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::GPSAltitudeRef,
+        info("GPSAltitudeRef", BYTE_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSAltitude,
+        info("GPSAltitude", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSTimeStamp,
+        info("GPSTimeStamp", RATIONAL_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::GPSSatellites,
+        info("GPSSatellites", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::GPSStatus,
+        info("GPSStatus", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSMeasureMode,
+        info("GPSMeasureMode", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSDOP,
+        info("GPSDOP", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSSpeedRef,
+        info("GPSSpeedRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSSpeed,
+        info("GPSSpeed", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSTrackRef,
+        info("GPSTrackRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSTrack,
+        info("GPSTrack", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSImgDirectionRef,
+        info("GPSImgDirectionRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSImgDirection,
+        info("GPSImgDirection", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSMapDatum,
+        info("GPSMapDatum", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::GPSDestLatitudeRef,
+        info("GPSDestLatitudeRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSDestLatitude,
+        info("GPSDestLatitude", RATIONAL_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::GPSDestLongitudeRef,
+        info("GPSDestLongitudeRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSDestLongitude,
+        info("GPSDestLongitude", RATIONAL_ONLY, ValueCount::Fixed(3)),
+    ),
+    (
+        Tag::GPSDestBearingRef,
+        info("GPSDestBearingRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSDestBearing,
+        info("GPSDestBearing", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSDestDistanceRef,
+        info("GPSDestDistanceRef", TEXT, ValueCount::Fixed(2)),
+    ),
+    (
+        Tag::GPSDestDistance,
+        info("GPSDestDistance", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSProcessingMethod,
+        info("GPSProcessingMethod", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::GPSAreaInformation,
+        info("GPSAreaInformation", UNDEFINED_ONLY, ValueCount::Variable),
+    ),
+    (
+        Tag::GPSDateStamp,
+        info("GPSDateStamp", TEXT, ValueCount::Fixed(11)),
+    ),
+    (
+        Tag::GPSDifferential,
+        info("GPSDifferential", SHORT_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::GPSHPositioningError,
+        info("GPSHPositioningError", RATIONAL_ONLY, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::InteroperabilityIndex,
+        info("InteroperabilityIndex", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::InteroperabilityVersion,
+        info(
+            "InteroperabilityVersion",
+            UNDEFINED_ONLY,
+            ValueCount::Fixed(4),
+        ),
+    ),
+    (
+        Tag::RelatedImageFileFormat,
+        info("RelatedImageFileFormat", TEXT, ValueCount::Variable),
+    ),
+    (
+        Tag::RelatedImageWidth,
+        info("RelatedImageWidth", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+    (
+        Tag::RelatedImageLength,
+        info("RelatedImageLength", SHORT_OR_LONG, ValueCount::Fixed(1)),
+    ),
+];
+
+const fn info(name: &'static str, types: &'static [u16], count: ValueCount) -> TagInfo {
+    TagInfo { name, types, count }
+}
 
-            // Digital Negative Specification, Version 1.4.0.0, page 59
-            // Application is described in detail in Chapter 6.
-            50965 => Tag::ForwardMatrix2,
+// Private tags applications registered at runtime, see Tag::register()
+static REGISTERED: RwLock<Vec<(Tag, TagInfo)>> = RwLock::new(Vec::new());
 
-            50966 => Tag::PreviewApplicationName,
+impl Tag {
+    /// What the specs say about the tag, or what the application registered for it, None for
+    /// tags nobody told this crate about
+    #[must_use]
+    pub fn info(self) -> Option<TagInfo> {
+        REGISTRY
+            .iter()
+            .find(|(tag, _)| *tag == self)
+            .map(|(_, info)| *info)
+            .or_else(|| {
+                registered()
+                    .iter()
+                    .find(|(tag, _)| *tag == self)
+                    .map(|(_, info)| *info)
+            })
+    }
 
-            50967 => Tag::PreviewApplicationVersion,
+    /// Describes a private tag, so that it gets a name, for printing and parsing, and is
+    /// validated like the tags the specs define. The tag is still `Tag::Other(number)`.
+    /// Registering a number again replaces what was registered before.
+    ///
+    /// # Errors
+    ///
+    /// If the specs already define a tag with that number, or another tag has that name
+    pub fn register(number: u16, info: TagInfo) -> Result<Tag, Error> {
+        let tag: Tag = Tag::new(number);
+        if tag != Tag::Other(number) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Tag {number} is already defined as \"{tag}\""),
+            ));
+        }
+        if let Ok(existing) = info.name.parse::<Tag>() {
+            if existing != tag {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Tag name \"{}\" is already taken", info.name),
+                ));
+            }
+        }
 
-            // Digital Negative Specification, Version 1.4.0.0, page 61
-            50969 => Tag::PreviewSettingsDigest,
+        let mut registered: RwLockWriteGuard<Vec<(Tag, TagInfo)>> =
+            REGISTERED.write().unwrap_or_else(PoisonError::into_inner);
+        registered.retain(|(registered_tag, _)| *registered_tag != tag);
+        registered.push((tag, info));
+        Ok(tag)
+    }
+}
 
-            // Digital Negative Specification, Version 1.4.0.0, page 61
-            50970 => Tag::PreviewColorSpace,
+// A panic while holding the lock cannot leave the list half changed, so poisoning is ignored
+fn registered() -> RwLockReadGuard<'static, Vec<(Tag, TagInfo)>> {
+    REGISTERED.read().unwrap_or_else(PoisonError::into_inner)
+}
 
-            50971 => Tag::PreviewDateTime,
+// Name the specs, or the application, give the tag, or its number for tags this crate does not
+// know about. Unknown tags with the number of an IFD0 tag can only come from GPS or
+// Interoperability IFDs, and would read back as the IFD0 tag, so they are written as GPS ones,
+// which read back as the same Tag::Other either way.
+impl Display for Tag {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.info(), self) {
+            (Some(info), _) => write!(formatter, "{}", info.name),
+            (None, Tag::Other(id)) if Tag::new(*id) != *self => {
+                write!(formatter, "{GPS_NAMESPACE}{id}")
+            }
+            (None, _) => write!(formatter, "{}", self.id()),
+        }
+    }
+}
 
-            50972 => Tag::RawImageDigest,
+#[cfg(feature = "serde")]
+impl serde::Serialize for Tag {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
 
-            // Digital Negative Specification, Version 1.4.0.0, page 67
-            51041 => Tag::NoiseProfile,
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Tag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Tag, D::Error> {
+        let string: String = String::deserialize(deserializer)?;
+        string.parse().map_err(serde::de::Error::custom)
+    }
+}
 
-            _ => Tag::Unknown,
+// Names, in any case, or numbers, decimal or hexadecimal with a "0x" prefix, so command lines and
+// configuration files can refer to tags either way. Numbers are looked up among IFD0 tags, unless
+// prefixed with "GPS:" or "Interoperability:".
+impl FromStr for Tag {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Tag, Error> {
+        let (new, number): (fn(u16) -> Tag, &str) = [
+            (GPS_NAMESPACE, Tag::new_gps as fn(u16) -> Tag),
+            (INTEROPERABILITY_NAMESPACE, Tag::new_interoperability),
+        ]
+        .into_iter()
+        .find_map(|(namespace, new)| {
+            let prefix: &str = string.get(..namespace.len())?;
+            prefix
+                .eq_ignore_ascii_case(namespace)
+                .then(|| (new, &string[namespace.len()..]))
+        })
+        .unwrap_or((Tag::new, string));
+        let parsed: Result<u16, ParseIntError> = match number.strip_prefix("0x") {
+            Some(hexadecimal) => u16::from_str_radix(hexadecimal, 16),
+            None => number.parse(),
+        };
+        if let Ok(parsed) = parsed {
+            return Ok(new(parsed));
         }
+        REGISTRY
+            .iter()
+            .find(|(_, info)| info.name.eq_ignore_ascii_case(string))
+            .map(|(tag, _)| *tag)
+            .or_else(|| {
+                registered()
+                    .iter()
+                    .find(|(_, info)| info.name.eq_ignore_ascii_case(string))
+                    .map(|(tag, _)| *tag)
+            })
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Not a tag name or number: {string}"),
+                )
+            })
     }
 }
@@ -13,37 +13,364 @@
  * 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
  */
 
+use data::Tag;
 use std::env::args;
-use std::fs::File;
-use std::io::{BufReader, Error, ErrorKind::InvalidData};
-use tiff_reader::{Dng, TiffReader};
+use std::fmt::Write as _;
+use std::fs::{read_dir, DirEntry, File};
+use std::io::{stdin, Error, ErrorKind::InvalidData, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use tiff_reader::{
+    capabilities, Camera, Dng, Field, FileSource, Ifd, ReaderOptions, Segment, StorageReport,
+    TiffReader, ValidationReport,
+};
 
 fn main() -> Result<(), Error> {
-    if let Some(file_name) = args().nth(1) {
-        let dng: Dng;
-        {
-            let mut tiff_reader: TiffReader<BufReader<File>> =
-                TiffReader::new(BufReader::new(File::open(file_name)?))?;
-            dng = tiff_reader.read_dng()?;
+    let arguments: Vec<String> = args().collect();
+    match arguments
+        .iter()
+        .skip(1)
+        .map(String::as_str)
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        ["capabilities"] => {
+            println!("{}", capabilities());
+            Ok(())
         }
-
-        println!("ifd0");
-        for tag in dng.ifd0.fields.keys() {
-            dbg!(tag);
-            if let Some(field) = dng.ifd0.fields.get(tag) {
-                dbg!(field);
-            }
+        ["info", file_name] => info(file_name),
+        ["manifest", directory] => manifest(Path::new(directory)),
+        ["previews", file_name] => previews(file_name),
+        ["storage", file_name] => storage(file_name),
+        ["strips", "-", index, output] => extract(
+            TiffReader::from_stream(stdin().lock())?,
+            index,
+            output,
+            false,
+        ),
+        ["strips", file_name, index, output] => {
+            extract(TiffReader::open(file_name)?, index, output, false)
         }
+        ["tiles", "-", index, output] => extract(
+            TiffReader::from_stream(stdin().lock())?,
+            index,
+            output,
+            true,
+        ),
+        ["tiles", file_name, index, output] => {
+            extract(TiffReader::open(file_name)?, index, output, true)
+        }
+        ["validate", file_name] => validate(file_name),
+        _ => Err(Error::new(
+            InvalidData,
+            "Usage: raw capabilities
+       raw info <file>
+       raw manifest <directory>
+       raw previews <file>
+       raw storage <file>
+       raw strips <file> <n> <output>
+       raw tiles <file> <n> <output>
+       raw validate <file>
+Use \"-\" instead of <file> to read from standard input",
+        )),
+    }
+}
+
+fn info(file_name: &str) -> Result<(), Error> {
+    let dng: Dng = if file_name == "-" {
+        TiffReader::from_stream(stdin().lock())?.read_dng()?
+    } else {
+        TiffReader::open(file_name)?.read_dng()?
+    };
 
-        println!("hires_ifd");
-        for tag in dng.hires_ifd.fields.keys() {
-            dbg!(tag);
-            if let Some(field) = dng.hires_ifd.fields.get(tag) {
-                dbg!(field);
+    print_ifd("ifd0", &dng.ifd0);
+    print_ifd("hires_ifd", &dng.hires_ifd);
+    if let Some(exif) = dng.exif() {
+        print_ifd("exif", exif);
+    }
+    if let Some(gps) = dng.gps() {
+        print_ifd("gps", gps);
+    }
+    if let Some(interoperability) = dng.interoperability() {
+        print_ifd("interoperability", interoperability);
+    }
+    Ok(())
+}
+
+// Prints, as CSV, one line for every file under directory, in path order so that the same tree
+// always gives the same manifest. Files that are not DNGs get a line too, saying why.
+fn manifest(directory: &Path) -> Result<(), Error> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    find_files(directory, &mut paths)?;
+    paths.sort();
+
+    println!(
+        "path,size,raw_data_unique_id,raw_image_digest,new_raw_image_digest,make,model,\
+         date_time_original,gps,error"
+    );
+    for path in paths {
+        // Only small values are needed, there is no point in reading previews and the like
+        let options: ReaderOptions = ReaderOptions::new().inline_value_limit(64);
+        let (size, dng): (String, Result<Dng, Error>) = match path.metadata() {
+            Ok(metadata) => (
+                metadata.len().to_string(),
+                TiffReader::open_with_options(&path, options)
+                    .and_then(|mut tiff_reader| tiff_reader.read_dng())
+                    .map_err(Error::from),
+            ),
+            // Unreadable files still get their line, with the reason in it
+            Err(error) => (String::new(), Err(error)),
+        };
+        let columns: Vec<String> = match dng {
+            Ok(dng) => {
+                let camera: Camera = dng.camera();
+                vec![
+                    hex(dng.ifd0.fields.get(&Tag::RawDataUniqueID)),
+                    hex(dng.ifd0.fields.get(&Tag::RawImageDigest)),
+                    hex(dng.ifd0.fields.get(&Tag::NewRawImageDigest)),
+                    camera.make.unwrap_or_default(),
+                    camera.model.unwrap_or_default(),
+                    match dng
+                        .exif()
+                        .and_then(|exif| exif.fields.get(&Tag::DateTimeOriginal))
+                    {
+                        Some(Field::Ascii(date_time, _)) => date_time.clone(),
+                        _ => String::new(),
+                    },
+                    dng.gps().is_some().to_string(),
+                    String::new(),
+                ]
             }
-        }
+            Err(error) => {
+                let mut columns: Vec<String> = vec![String::new(); 7];
+                columns.push(error.to_string());
+                columns
+            }
+        };
+        println!(
+            "{},{size},{}",
+            csv(&path.to_string_lossy()),
+            columns
+                .iter()
+                .map(|column| csv(column))
+                .collect::<Vec<String>>()
+                .join(",")
+        );
+    }
+    Ok(())
+}
+
+// Prints every image but the raw one, with what its JPEG stream says about it, if any
+fn previews(file_name: &str) -> Result<(), Error> {
+    let mut tiff_reader: TiffReader<FileSource> = TiffReader::open(file_name)?;
+    let dng: Dng = tiff_reader.read_dng()?;
+
+    println!(
+        "image,compression,width,length,bytes,jpeg width,jpeg height,precision,process,subsampling"
+    );
+    for preview in tiff_reader.previews(&dng) {
+        let jpeg: [String; 5] = match &preview.jpeg {
+            Some(jpeg) => [
+                jpeg.width.to_string(),
+                jpeg.height.to_string(),
+                jpeg.precision.to_string(),
+                jpeg.process().to_string(),
+                jpeg.subsampling().unwrap_or_default().to_string(),
+            ],
+            None => Default::default(),
+        };
+        println!(
+            "{},{},{},{},{},{}",
+            preview.name,
+            preview.compression,
+            preview.width,
+            preview.length,
+            preview
+                .bytes
+                .map(|bytes| bytes.to_string())
+                .unwrap_or_default(),
+            jpeg.join(","),
+        );
+    }
+    Ok(())
+}
+
+// Prints where the bytes of the file go, one line per image and then totals
+fn storage(file_name: &str) -> Result<(), Error> {
+    let file_size: u64 = Path::new(file_name).metadata()?.len();
+    let report: StorageReport = TiffReader::open(file_name)?
+        .read_dng()?
+        .storage_report(file_size)?;
+
+    println!("image,kind,compression,width,length,compressed,uncompressed,ratio");
+    for image in &report.images {
+        println!(
+            "{},{},{},{},{},{},{},{}",
+            image.name,
+            if image.raw { "raw" } else { "preview" },
+            image.compression,
+            image.width,
+            image.length,
+            image.compressed_bytes,
+            image.uncompressed_bytes,
+            image
+                .ratio()
+                .map(|ratio| format!("{ratio:.2}"))
+                .unwrap_or_default(),
+        );
+    }
+    println!();
+    println!("raw data: {} bytes", report.raw_bytes());
+    println!("previews: {} bytes", report.preview_bytes());
+    println!("metadata and other: {} bytes", report.other_bytes());
+    println!("file: {} bytes", report.file_size);
+    Ok(())
+}
+
+// Prints everything wrong with the file, one line per problem, reading it leniently so that
+// violations of the specs show up as warnings rather than stopping at the first one
+fn validate(file_name: &str) -> Result<(), Error> {
+    let options: ReaderOptions = ReaderOptions::new().strict(false);
+    let report: ValidationReport = TiffReader::open_with_options(file_name, options)?
+        .read_dng()?
+        .validate();
+
+    println!("severity,ifd,offset,tag,message");
+    for issue in &report.issues {
+        println!(
+            "{},{},{},{},{}",
+            issue.severity,
+            issue.ifd,
+            issue
+                .offset
+                .map(|offset| offset.to_string())
+                .unwrap_or_default(),
+            issue.tag.map(|tag| tag.to_string()).unwrap_or_default(),
+            issue.error
+        );
+    }
+    if report.is_valid() {
+        Ok(())
     } else {
-        return Err(Error::new(InvalidData, "Please specify a file"));
+        Err(Error::new(InvalidData, "File is not a valid DNG"))
+    }
+}
+
+fn find_files(directory: &Path, paths: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in read_dir(directory)? {
+        let entry: DirEntry = entry?;
+        let path: PathBuf = entry.path();
+        // Symbolic links to directories are not followed, one to a parent would never end
+        if entry.file_type()?.is_dir() {
+            find_files(&path, paths)?;
+        } else if !path.is_dir() {
+            paths.push(path);
+        }
     }
     Ok(())
 }
+
+fn hex(field: Option<&Field>) -> String {
+    match field {
+        Some(Field::Byte(bytes)) => bytes.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        }),
+        _ => String::new(),
+    }
+}
+
+// Quotes values that would otherwise break the line into the wrong columns
+fn csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Dumps strip or tile n of the full resolution image, still compressed, to output, after a line
+// describing how to decode it, so problem files can be turned into decompressor test cases.
+fn extract<R: Read + Seek>(
+    mut tiff_reader: TiffReader<R>,
+    n: &str,
+    output: &str,
+    tiles: bool,
+) -> Result<(), Error> {
+    let n: usize = n
+        .parse()
+        .map_err(|_| Error::new(InvalidData, format!("Invalid strip or tile number: {n}")))?;
+    let dng: Dng = tiff_reader.read_dng()?;
+    let ifd: &Ifd = &dng.hires_ifd;
+
+    if tiles != ifd.fields.contains_key(&Tag::TileOffsets) {
+        return Err(Error::new(
+            InvalidData,
+            format!(
+                "Image is not stored in {}",
+                if tiles { "tiles" } else { "strips" }
+            ),
+        ));
+    }
+    let segments: Vec<Segment> = ifd.segments()?;
+    let Some(segment) = segments.get(n) else {
+        return Err(Error::new(
+            InvalidData,
+            format!("No strip or tile {n}, there are {}", segments.len()),
+        ));
+    };
+    // Edge tiles are padded, so they always have the same size, but the last strip may be
+    // shorter than the others
+    let (width, length): (u32, u32) = if tiles {
+        (segment.stored_width, segment.stored_length)
+    } else {
+        (segment.width, segment.length)
+    };
+    let bits_per_sample: Vec<String> = numbers(ifd, Tag::BitsPerSample)
+        .unwrap_or_else(|_| vec![1])
+        .iter()
+        .map(u64::to_string)
+        .collect();
+
+    let data: Vec<u8> = tiff_reader.read_bytes(
+        segment.offset,
+        usize::try_from(segment.byte_count).map_err(|error| Error::new(InvalidData, error))?,
+    )?;
+
+    let mut file: File = File::create(output)?;
+    writeln!(
+        file,
+        "compression={} width={width} length={length} bits_per_sample={} samples_per_pixel={}",
+        number(ifd, Tag::Compression).unwrap_or(1),
+        bits_per_sample.join(","),
+        number(ifd, Tag::SamplesPerPixel).unwrap_or(1),
+    )?;
+    file.write_all(&data)
+}
+
+fn numbers(ifd: &Ifd, tag: Tag) -> Result<Vec<u64>, Error> {
+    match ifd.fields.get(&tag) {
+        Some(Field::Short(values)) => Ok(values.iter().map(|value| u64::from(*value)).collect()),
+        Some(Field::Long(values)) => Ok(values.iter().map(|value| u64::from(*value)).collect()),
+        _ => Err(Error::new(
+            InvalidData,
+            format!("\"{tag:?}\" is missing or is not a number"),
+        )),
+    }
+}
+
+fn number(ifd: &Ifd, tag: Tag) -> Result<u64, Error> {
+    numbers(ifd, tag)?
+        .first()
+        .copied()
+        .ok_or_else(|| Error::new(InvalidData, format!("\"{tag:?}\" is empty")))
+}
+
+// Tags in numeric order, so that the same file always prints the same
+fn print_ifd(name: &str, ifd: &Ifd) {
+    println!("{name}");
+    let mut fields: Vec<(&Tag, &Field)> = ifd.fields.iter().collect();
+    fields.sort_by_key(|(tag, _)| **tag);
+    for (tag, field) in fields {
+        println!("{tag}: {}", field.to_string_lossy());
+    }
+}
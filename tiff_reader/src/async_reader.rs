@@ -0,0 +1,108 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use crate::{Dng, ReaderOptions, TiffError, TiffReader};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+// Bytes fetched from the source at a time
+const BLOCK_SIZE: u64 = 64 * 1024;
+
+/// Reads a DNG from an asynchronous source, such as an upload, without blocking the executor.
+///
+/// Parsing is done by the same code `TiffReader` uses for everything else, over the blocks of the
+/// source fetched so far. Reading a block not fetched yet stops it; the block is then fetched,
+/// asynchronously, and parsing starts over. Only blocks with data the reader needs are ever
+/// fetched, 64 KiB at a time, so however large the source, what is kept in memory is bounded
+/// by what `read_dng()` would read from it, which `ReaderOptions` limits.
+///
+/// # Errors
+///
+/// Those caused by the underlying reader, plus the ones `TiffReader::read_dng()` returns
+pub async fn read_dng_async<R: AsyncRead + AsyncSeek + Unpin>(
+    mut reader: R,
+    options: ReaderOptions,
+) -> Result<Dng, TiffError> {
+    let length: u64 = reader.seek(SeekFrom::End(0)).await?;
+    let mut blocks: HashMap<u64, Vec<u8>> = HashMap::new();
+    loop {
+        let missing: Cell<Option<u64>> = Cell::new(None);
+        let source: FetchedBlocks = FetchedBlocks {
+            blocks: &blocks,
+            length,
+            position: 0,
+            missing: &missing,
+        };
+        let result: Result<Dng, TiffError> = TiffReader::with_options(source, options.clone())
+            .and_then(|mut tiff_reader| tiff_reader.read_dng());
+        // Even a successful read may have left out something it could not read
+        let Some(index) = missing.get() else {
+            return result;
+        };
+
+        let start: u64 = index * BLOCK_SIZE;
+        let size: usize = usize::try_from(BLOCK_SIZE.min(length - start)).unwrap_or(0);
+        let mut block: Vec<u8> = vec![0; size];
+        reader.seek(SeekFrom::Start(start)).await?;
+        reader.read_exact(&mut block).await?;
+        blocks.insert(index, block);
+    }
+}
+
+// Read and Seek over the blocks of a source fetched so far. Reads of a block that is missing fail,
+// leaving its index in missing.
+struct FetchedBlocks<'a> {
+    blocks: &'a HashMap<u64, Vec<u8>>,
+    length: u64,
+    position: u64,
+    missing: &'a Cell<Option<u64>>,
+}
+
+impl Read for FetchedBlocks<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        if self.position >= self.length || buffer.is_empty() {
+            return Ok(0);
+        }
+        let index: u64 = self.position / BLOCK_SIZE;
+        let Some(block) = self.blocks.get(&index) else {
+            self.missing.set(Some(index));
+            return Err(Error::new(ErrorKind::WouldBlock, "Block not fetched yet"));
+        };
+        // Blocks are BLOCK_SIZE long, but maybe the last one
+        let start: usize = usize::try_from(self.position % BLOCK_SIZE).unwrap_or(usize::MAX);
+        let data: &[u8] = block.get(start..).unwrap_or(&[]);
+        let count: usize = data.len().min(buffer.len());
+        buffer[..count].copy_from_slice(&data[..count]);
+        self.position += u64::try_from(count).unwrap_or(u64::MAX);
+        Ok(count)
+    }
+}
+
+impl Seek for FetchedBlocks<'_> {
+    fn seek(&mut self, position: SeekFrom) -> Result<u64, Error> {
+        let (base, delta): (u64, i64) = match position {
+            SeekFrom::Start(offset) => (offset, 0),
+            SeekFrom::Current(delta) => (self.position, delta),
+            SeekFrom::End(delta) => (self.length, delta),
+        };
+        self.position = base.checked_add_signed(delta).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "Invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
@@ -0,0 +1,101 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::{Dng, Field, Ifd};
+
+// Black level of every pixel of the raw image. DNG spreads it over four tags: BlackLevel holds a
+// pattern, BlackLevelRepeatDim rows by columns of it for each sample, repeating over the image,
+// to which BlackLevelDeltaH adds an amount per column and BlackLevelDeltaV one per row. Medium
+// format backs use the deltas to even out their sensors, so shadows come out clean only with all
+// four applied. Rows and columns count from the top left corner of ActiveArea, as the deltas do.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlackLevels {
+    pub repeat_rows: u16,
+    pub repeat_columns: u16,
+    pub samples_per_pixel: u16,
+    // BlackLevel in row, column, sample order
+    pub pattern: Vec<f64>,
+    // One per column, missing ones are zero
+    pub delta_h: Vec<f64>,
+    // One per row, missing ones are zero
+    pub delta_v: Vec<f64>,
+}
+
+impl BlackLevels {
+    /// Black level of the given sample of the pixel at row and column
+    #[must_use]
+    pub fn at(&self, row: usize, column: usize, sample: usize) -> f64 {
+        let rows: usize = usize::from(self.repeat_rows.max(1));
+        let columns: usize = usize::from(self.repeat_columns.max(1));
+        let samples: usize = usize::from(self.samples_per_pixel.max(1));
+        let index: usize = ((row % rows) * columns + column % columns) * samples + sample % samples;
+        self.pattern.get(index).copied().unwrap_or(0.0)
+            + self.delta_h.get(column).copied().unwrap_or(0.0)
+            + self.delta_v.get(row).copied().unwrap_or(0.0)
+    }
+
+    /// Value with its black level taken away, the first step of linearization. Not clipped, as
+    /// noise below black carries information averaging can still use.
+    #[must_use]
+    pub fn subtract(&self, value: f64, row: usize, column: usize, sample: usize) -> f64 {
+        value - self.at(row, column, sample)
+    }
+}
+
+impl Dng {
+    /// Black levels of the raw image, zero everywhere when it has none of the tags. None if the
+    /// tags are inconsistent, such as a pattern with fewer values than its dimensions call for.
+    #[must_use]
+    pub fn black_levels(&self) -> Option<BlackLevels> {
+        black_levels(&self.hires_ifd)
+    }
+}
+
+fn black_levels(ifd: &Ifd) -> Option<BlackLevels> {
+    let samples_per_pixel: u16 =
+        u16::try_from(ifd.number(Tag::SamplesPerPixel).unwrap_or(1)).ok()?;
+
+    // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 1, 1
+    let (repeat_rows, repeat_columns): (u16, u16) = match ifd.fields.get(&Tag::BlackLevelRepeatDim)
+    {
+        None => (1, 1),
+        Some(Field::Short(dim)) if dim.len() == 2 && dim[0] > 0 && dim[1] > 0 => (dim[0], dim[1]),
+        Some(_) => return None,
+    };
+
+    // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 0
+    let size: usize =
+        usize::from(repeat_rows) * usize::from(repeat_columns) * usize::from(samples_per_pixel);
+    let pattern: Vec<f64> = match ifd.fields.get(&Tag::BlackLevel) {
+        None => vec![0.0; size],
+        Some(field) => field.to_f64s().filter(|pattern| pattern.len() == size)?,
+    };
+
+    let deltas = |tag: Tag| -> Option<Vec<f64>> {
+        match ifd.fields.get(&tag) {
+            None => Some(Vec::new()),
+            Some(field) => field.to_f64s(),
+        }
+    };
+
+    Some(BlackLevels {
+        repeat_rows,
+        repeat_columns,
+        samples_per_pixel,
+        pattern,
+        delta_h: deltas(Tag::BlackLevelDeltaH)?,
+        delta_v: deltas(Tag::BlackLevelDeltaV)?,
+    })
+}
@@ -0,0 +1,157 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::{Short, Tag};
+
+use crate::{CfaPattern, Compression, Field, Fields, Ifd, Photometric, TiffError};
+
+// IFDs for the kinds of image DNG files hold, started with every tag the specs require for them
+// and values consistent with each other, rather than put together tag by tag. Where the image
+// data is stored is only known once laid out in a file, so that comes last, from strips().
+pub struct IfdBuilder {
+    ifd: Ifd,
+    // Rows per strip, offsets and byte counts, once strips() is called
+    strips: Option<(u32, Vec<u32>, Vec<u32>)>,
+}
+
+impl IfdBuilder {
+    /// Full resolution CFA raw image, uncompressed unless `compression()` says otherwise, with
+    /// `WhiteLevel` the largest value bits per sample can hold
+    #[must_use]
+    pub fn raw_image(width: u32, length: u32, cfa: &CfaPattern, bits: Short) -> IfdBuilder {
+        let white_level: u32 = u32::try_from((1u64 << bits.min(32)) - 1).unwrap_or(u32::MAX);
+        IfdBuilder::image(width, length, Photometric::Cfa)
+            .field(Tag::NewSubFileType, Field::Long(vec![0]))
+            .field(Tag::BitsPerSample, Field::Short(vec![bits]))
+            .field(Tag::SamplesPerPixel, Field::Short(vec![1]))
+            .field(
+                Tag::CFARepeatPatternDim,
+                Field::Short(vec![cfa.rows, cfa.columns]),
+            )
+            .field(Tag::CFAPattern, Field::Byte(cfa.colors.clone()))
+            .field(Tag::CFAPlaneColor, Field::Byte(cfa.plane_colors.clone()))
+            .field(
+                Tag::CFALayout,
+                Field::Short(vec![Short::try_from(cfa.layout).unwrap_or(1)]),
+            )
+            .field(Tag::WhiteLevel, Field::Long(vec![white_level]))
+    }
+
+    /// Reduced resolution 8-bit RGB image, as TIFF/EP recommends for IFD0, uncompressed unless
+    /// `compression()` says otherwise
+    #[must_use]
+    pub fn thumbnail(width: u32, length: u32) -> IfdBuilder {
+        IfdBuilder::image(width, length, Photometric::Rgb)
+            .field(Tag::NewSubFileType, Field::Long(vec![1]))
+            .field(Tag::BitsPerSample, Field::Short(vec![8, 8, 8]))
+            .field(Tag::SamplesPerPixel, Field::Short(vec![3]))
+    }
+
+    // Tags every image needs, whatever its kind
+    fn image(width: u32, length: u32, photometric: Photometric) -> IfdBuilder {
+        IfdBuilder {
+            ifd: Ifd {
+                fields: Fields::new(),
+                offset: 0,
+                position: 0,
+                sub_ifds: Vec::new(),
+                exif: None,
+                gps: None,
+                interoperability: None,
+                duplicates: Vec::new(),
+            },
+            strips: None,
+        }
+        .field(Tag::ImageWidth, Field::Long(vec![width]))
+        .field(Tag::ImageLength, Field::Long(vec![length]))
+        .field(Tag::Compression, Field::Short(vec![1]))
+        .field(
+            Tag::PhotometricInterpretation,
+            Field::Short(vec![photometric.into()]),
+        )
+        .field(Tag::PlanarConfiguration, Field::Short(vec![1]))
+    }
+
+    #[must_use]
+    pub fn compression(self, compression: Compression) -> IfdBuilder {
+        self.field(Tag::Compression, Field::Short(vec![compression.into()]))
+    }
+
+    /// Where the image data is: rows in each strip, but maybe the last, and the offset and size
+    /// of each strip
+    #[must_use]
+    pub fn strips(
+        mut self,
+        rows_per_strip: u32,
+        offsets: Vec<u32>,
+        byte_counts: Vec<u32>,
+    ) -> IfdBuilder {
+        self.strips = Some((rows_per_strip, offsets, byte_counts));
+        self
+    }
+
+    /// Any other field, replacing whatever the builder set for the tag
+    #[must_use]
+    pub fn field(mut self, tag: Tag, field: Field) -> IfdBuilder {
+        self.ifd.insert(tag, field);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Missing strips, or not as many as the image length calls for, an inconsistent CFA pattern,
+    /// and any field whose type or count the tag registry does not allow, see `Ifd::validate()`
+    pub fn build(mut self) -> Result<Ifd, TiffError> {
+        let Some((rows_per_strip, offsets, byte_counts)) = self.strips else {
+            return Err(TiffError::InvalidData(
+                "Image has no strips, see IfdBuilder::strips()".to_string(),
+            ));
+        };
+        if rows_per_strip == 0 {
+            return Err(TiffError::InvalidData(
+                "RowsPerStrip cannot be 0".to_string(),
+            ));
+        }
+        let length: u32 = self.ifd.number(Tag::ImageLength).unwrap_or(0);
+        let strips: usize = usize::try_from(length.div_ceil(rows_per_strip)).unwrap_or(usize::MAX);
+        if offsets.len() != strips || byte_counts.len() != strips {
+            return Err(TiffError::InvalidData(format!(
+                "Image of {length} rows, {rows_per_strip} per strip, needs {strips} strips, found {} offsets and {} byte counts",
+                offsets.len(),
+                byte_counts.len()
+            )));
+        }
+        self.ifd
+            .insert(Tag::RowsPerStrip, Field::Long(vec![rows_per_strip]));
+        self.ifd.insert(Tag::StripOffsets, Field::Long(offsets));
+        self.ifd
+            .insert(Tag::StripByteCounts, Field::Long(byte_counts));
+
+        if let Some(Field::Short(dim)) = self.ifd.get(Tag::CFARepeatPatternDim) {
+            let positions: usize = dim.iter().map(|size| usize::from(*size)).product();
+            let colors: Option<usize> = match self.ifd.get(Tag::CFAPattern) {
+                Some(Field::Byte(colors)) => Some(colors.len()),
+                _ => None,
+            };
+            if positions == 0 || colors != Some(positions) {
+                return Err(TiffError::InvalidData(
+                    "CFAPattern does not fill CFARepeatPatternDim".to_string(),
+                ));
+            }
+        }
+        match self.ifd.validate().into_iter().next() {
+            Some(violation) => Err(violation),
+            None => Ok(self.ifd),
+        }
+    }
+}
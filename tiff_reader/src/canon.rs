@@ -0,0 +1,202 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::{Short, Sshort, Tag};
+
+use crate::{Field, Ifd, TiffError, TiffReader};
+use std::io::{Read, Seek};
+
+// Canon maker note: an IFD with Canon's own tag numbers, in the byte order of the file, whose
+// value offsets count from the start of the file as those of any other IFD do. Only the two
+// arrays of shot settings every Canon camera writes are decoded, the IFD keeps the rest as read.
+// White balance coefficients are left out, ColorData lays them out differently for each model.
+pub struct CanonMakerNote {
+    pub ifd: Ifd,
+    pub camera_settings: Option<CameraSettings>,
+    pub shot_info: Option<ShotInfo>,
+}
+
+// Canon tag 0x0001, an array of SHORTs numbered from 1, whose first one is the size of the array
+// in bytes. Values are Canon's own codes, as ExifTool documents them. Those the camera did not
+// set, -1, and those past the end of the array, as with older cameras, are None.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CameraSettings {
+    // 1 macro, 2 normal
+    pub macro_mode: Option<Sshort>,
+    // 1 economy, 2 normal, 3 fine, 4 RAW, 5 superfine
+    pub quality: Option<Sshort>,
+    // 0 off, 1 auto, 2 on, 3 red-eye reduction and so on
+    pub flash_mode: Option<Sshort>,
+    // 0 single, 1 continuous, 2 movie and so on
+    pub continuous_drive: Option<Sshort>,
+    // 0 one-shot AF, 1 AI servo AF, 2 AI focus AF, 3 manual focus and so on
+    pub focus_mode: Option<Sshort>,
+    // 0 default, 1 spot, 2 average, 3 evaluative, 4 partial, 5 center-weighted average
+    pub metering_mode: Option<Sshort>,
+    // 0x2005 manual AF point selection, 0x3000 none, 0x3001 auto and so on
+    pub af_point: Option<Sshort>,
+    // 0 easy, 1 program, 2 shutter priority, 3 aperture priority, 4 manual and so on
+    pub exposure_mode: Option<Sshort>,
+    // Canon's lens ID, the same number for lenses that cannot be told apart
+    pub lens_type: Option<Short>,
+    // Focal lengths of the lens, in focal_units per mm
+    pub max_focal_length: Option<Short>,
+    pub min_focal_length: Option<Short>,
+    pub focal_units: Option<Short>,
+    // 0 off, 1 on, 2 shoot only, 3 panning and so on
+    pub image_stabilization: Option<Sshort>,
+}
+
+// Canon tag 0x0004, laid out as CameraSettings. Exposure values are in Canon's 1/32 EV steps.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ShotInfo {
+    // ISO speed as 100 × 2^(value/32) for auto_iso, in percent, and 100/32 × 2^(value/32) for
+    // base_iso
+    pub auto_iso: Option<Sshort>,
+    pub base_iso: Option<Sshort>,
+    pub measured_ev: Option<Sshort>,
+    pub target_aperture: Option<Sshort>,
+    pub target_exposure_time: Option<Sshort>,
+    pub exposure_compensation: Option<Sshort>,
+    // 0 auto, 1 daylight, 2 cloudy, 3 tungsten, 4 fluorescent, 5 flash, 6 custom and so on
+    pub white_balance: Option<Sshort>,
+    pub sequence_number: Option<Sshort>,
+    // Degrees Celsius plus 128, only written by some cameras
+    pub camera_temperature: Option<Sshort>,
+    // Bit mask of the AF points in focus, for cameras with up to 16 of them
+    pub af_points_in_focus: Option<Sshort>,
+    // In cm
+    pub focus_distance_upper: Option<Short>,
+    pub focus_distance_lower: Option<Short>,
+    pub f_number: Option<Sshort>,
+    pub exposure_time: Option<Sshort>,
+}
+
+// Canon tag numbers
+const CAMERA_SETTINGS: u16 = 0x0001;
+const SHOT_INFO: u16 = 0x0004;
+
+impl<R: Read + Seek> TiffReader<R> {
+    /// Canon maker note pointed to by the Exif IFD of ifd0, the first IFD of a DNG or of any other
+    /// TIFF based file, such as CR2. None if `Make` does not say Canon, or there is no Exif IFD or
+    /// maker note. Maker notes DNG converters moved into `DNGPrivateData` are not looked for.
+    ///
+    /// # Errors
+    ///
+    /// Those caused by the underlying reader, plus a maker note that is not an IFD
+    pub fn canon_maker_note(&mut self, ifd0: &Ifd) -> Result<Option<CanonMakerNote>, TiffError> {
+        // Make may not have been loaded yet, in lazy mode
+        let canon: bool = match ifd0.fields.get(&Tag::Make) {
+            Some(&Field::Lazy {
+                type_,
+                count,
+                offset,
+            }) => is_canon(&self.load_field(type_, count, offset)?),
+            Some(make) => is_canon(make),
+            None => false,
+        };
+        let Some(exif) = ifd0.exif.as_deref() else {
+            return Ok(None);
+        };
+        if !canon || exif.position == 0 {
+            return Ok(None);
+        }
+
+        // Loaded values no longer say where they are in the file. Reading the Exif IFD again,
+        // loading none of them, gives where the maker note is.
+        let inline_value_limit: usize = std::mem::replace(&mut self.options.inline_value_limit, 0);
+        let result: Result<Ifd, TiffError> = self.read_ifd_at(exif.position);
+        self.options.inline_value_limit = inline_value_limit;
+        let offset: u64 = match result?.fields.get(&Tag::MakerNote) {
+            None => return Ok(None),
+            Some(Field::Lazy { offset, .. }) => *offset,
+            Some(_) => {
+                return Err(TiffError::InvalidData(
+                    "Canon maker note is too short to be an IFD".to_string(),
+                ))
+            }
+        };
+
+        // In the byte order of the file, but with every value loaded whatever the options say
+        let ifd: Ifd = self
+            .read_ifd_at_with_byte_order(offset, self.endianness)
+            .map_err(|error| error.with_tag(Tag::MakerNote))?;
+        let camera_settings: Option<CameraSettings> =
+            values(&ifd, CAMERA_SETTINGS).map(|values| CameraSettings {
+                macro_mode: signed(&values, 1),
+                quality: signed(&values, 3),
+                flash_mode: signed(&values, 4),
+                continuous_drive: signed(&values, 5),
+                focus_mode: signed(&values, 7),
+                metering_mode: signed(&values, 17),
+                af_point: signed(&values, 19),
+                exposure_mode: signed(&values, 20),
+                lens_type: unsigned(&values, 22),
+                max_focal_length: unsigned(&values, 23),
+                min_focal_length: unsigned(&values, 24),
+                focal_units: unsigned(&values, 25),
+                image_stabilization: signed(&values, 34),
+            });
+        let shot_info: Option<ShotInfo> = values(&ifd, SHOT_INFO).map(|values| ShotInfo {
+            auto_iso: signed(&values, 1),
+            base_iso: signed(&values, 2),
+            measured_ev: signed(&values, 3),
+            target_aperture: signed(&values, 4),
+            target_exposure_time: signed(&values, 5),
+            exposure_compensation: signed(&values, 6),
+            white_balance: signed(&values, 7),
+            sequence_number: signed(&values, 9),
+            camera_temperature: signed(&values, 12),
+            af_points_in_focus: signed(&values, 14),
+            focus_distance_upper: unsigned(&values, 19),
+            focus_distance_lower: unsigned(&values, 20),
+            f_number: signed(&values, 21),
+            exposure_time: signed(&values, 22),
+        });
+        Ok(Some(CanonMakerNote {
+            ifd,
+            camera_settings,
+            shot_info,
+        }))
+    }
+}
+
+fn is_canon(make: &Field) -> bool {
+    matches!(make, Field::Ascii(make, _) | Field::Utf8(make, _) if make.starts_with("Canon"))
+}
+
+// Canon writes the arrays as SHORT, though most of their values are signed
+fn values(ifd: &Ifd, id: u16) -> Option<Vec<Short>> {
+    match ifd.fields.get(&Tag::new(id))? {
+        Field::Short(values) => Some(values.clone()),
+        Field::Sshort(values) => Some(
+            values
+                .iter()
+                .map(|value| Short::from_ne_bytes(value.to_ne_bytes()))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn signed(values: &[Short], index: usize) -> Option<Sshort> {
+    let value: Sshort = Sshort::from_ne_bytes(values.get(index)?.to_ne_bytes());
+    Some(value).filter(|value| *value != -1)
+}
+
+fn unsigned(values: &[Short], index: usize) -> Option<Short> {
+    values
+        .get(index)
+        .copied()
+        .filter(|value| *value != Short::MAX)
+}
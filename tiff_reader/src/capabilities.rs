@@ -0,0 +1,93 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::fmt::{Display, Formatter};
+
+// What this build of the reader can do, given the features it was compiled with, so hosts can
+// tell users up front instead of failing halfway, and bug reports can say what was available.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Capabilities {
+    // File formats read
+    pub formats: Vec<&'static str>,
+    // Compression values whose strips and tiles can be decoded, see TiffReader::read_raw_image().
+    // Image data in any other is only handed out as is, see TiffReader::read_bytes().
+    pub compressions: Vec<u16>,
+    // Where files can be read from
+    pub sources: Vec<&'static str>,
+    // Processing steps from raw data to a rendered image, none for now
+    pub pipeline_stages: Vec<&'static str>,
+    // Cargo features enabled
+    pub features: Vec<&'static str>,
+}
+
+/// What this build supports
+#[must_use]
+pub fn capabilities() -> Capabilities {
+    let mut sources: Vec<&'static str> = vec!["file", "stream"];
+    if cfg!(feature = "mmap") {
+        sources.push("memory map");
+    }
+    if cfg!(feature = "tokio") {
+        sources.push("tokio");
+    }
+    if cfg!(feature = "http") {
+        sources.push("http");
+    }
+
+    let features: Vec<&'static str> = [
+        ("btree", cfg!(feature = "btree")),
+        ("chrono", cfg!(feature = "chrono")),
+        ("fuzzing", cfg!(feature = "fuzzing")),
+        ("http", cfg!(feature = "http")),
+        ("mmap", cfg!(feature = "mmap")),
+        ("serde", cfg!(feature = "serde")),
+        ("tokio", cfg!(feature = "tokio")),
+    ]
+    .into_iter()
+    .filter_map(|(feature, enabled)| enabled.then_some(feature))
+    .collect();
+
+    Capabilities {
+        formats: vec!["TIFF", "DNG"],
+        compressions: vec![1],
+        sources,
+        pipeline_stages: Vec::new(),
+        features,
+    }
+}
+
+impl Display for Capabilities {
+    fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        let compressions: Vec<String> = self.compressions.iter().map(u16::to_string).collect();
+        writeln!(formatter, "formats: {}", list(&self.formats))?;
+        writeln!(formatter, "compressions: {}", list(&compressions))?;
+        writeln!(formatter, "sources: {}", list(&self.sources))?;
+        writeln!(
+            formatter,
+            "pipeline stages: {}",
+            list(&self.pipeline_stages)
+        )?;
+        write!(formatter, "features: {}", list(&self.features))
+    }
+}
+
+fn list<T: AsRef<str>>(items: &[T]) -> String {
+    if items.is_empty() {
+        return "none".to_string();
+    }
+    items
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
@@ -0,0 +1,267 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::Ifd;
+
+// Tags whose values are codes, each meaning one of a few things, decoded so callers can match on
+// names instead of magic numbers. Codes no spec defines are kept as they are, in Other.
+
+// How image data is compressed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compression {
+    Uncompressed,
+    CcittHuffman,
+    CcittGroup3,
+    CcittGroup4,
+    Lzw,
+    // JPEG as TIFF 6.0 first defined it, long obsolete
+    OldJpeg,
+    // Lossless JPEG in DNG raw images, baseline JPEG elsewhere
+    Jpeg,
+    Deflate,
+    PackBits,
+    // DNG 1.4, for previews and lossy compressed raw images
+    LossyJpeg,
+    // DNG 1.7
+    JpegXl,
+    Other(u16),
+}
+
+impl From<u16> for Compression {
+    fn from(code: u16) -> Compression {
+        match code {
+            1 => Compression::Uncompressed,
+            2 => Compression::CcittHuffman,
+            3 => Compression::CcittGroup3,
+            4 => Compression::CcittGroup4,
+            5 => Compression::Lzw,
+            6 => Compression::OldJpeg,
+            7 => Compression::Jpeg,
+            // 8 is the registered code, 32946 the one in use before
+            8 | 32946 => Compression::Deflate,
+            32773 => Compression::PackBits,
+            34892 => Compression::LossyJpeg,
+            52546 => Compression::JpegXl,
+            code => Compression::Other(code),
+        }
+    }
+}
+
+impl From<Compression> for u16 {
+    fn from(compression: Compression) -> u16 {
+        match compression {
+            Compression::Uncompressed => 1,
+            Compression::CcittHuffman => 2,
+            Compression::CcittGroup3 => 3,
+            Compression::CcittGroup4 => 4,
+            Compression::Lzw => 5,
+            Compression::OldJpeg => 6,
+            Compression::Jpeg => 7,
+            Compression::Deflate => 8,
+            Compression::PackBits => 32773,
+            Compression::LossyJpeg => 34892,
+            Compression::JpegXl => 52546,
+            Compression::Other(code) => code,
+        }
+    }
+}
+
+// Color space of image data
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Photometric {
+    WhiteIsZero,
+    BlackIsZero,
+    Rgb,
+    Palette,
+    TransparencyMask,
+    Cmyk,
+    YCbCr,
+    CieLab,
+    // Color filter array, the raw data of most cameras
+    Cfa,
+    // Raw data already demosaiced, or from sensors without a color filter array
+    LinearRaw,
+    // DNG 1.5 depth maps
+    Depth,
+    Other(u16),
+}
+
+impl From<u16> for Photometric {
+    fn from(code: u16) -> Photometric {
+        match code {
+            0 => Photometric::WhiteIsZero,
+            1 => Photometric::BlackIsZero,
+            2 => Photometric::Rgb,
+            3 => Photometric::Palette,
+            4 => Photometric::TransparencyMask,
+            5 => Photometric::Cmyk,
+            6 => Photometric::YCbCr,
+            8 => Photometric::CieLab,
+            32803 => Photometric::Cfa,
+            34892 => Photometric::LinearRaw,
+            51177 => Photometric::Depth,
+            code => Photometric::Other(code),
+        }
+    }
+}
+
+impl From<Photometric> for u16 {
+    fn from(photometric: Photometric) -> u16 {
+        match photometric {
+            Photometric::WhiteIsZero => 0,
+            Photometric::BlackIsZero => 1,
+            Photometric::Rgb => 2,
+            Photometric::Palette => 3,
+            Photometric::TransparencyMask => 4,
+            Photometric::Cmyk => 5,
+            Photometric::YCbCr => 6,
+            Photometric::CieLab => 8,
+            Photometric::Cfa => 32803,
+            Photometric::LinearRaw => 34892,
+            Photometric::Depth => 51177,
+            Photometric::Other(code) => code,
+        }
+    }
+}
+
+// Where the first row and column of the image are, once displayed. TopLeft needs no change,
+// RightTop needs turning 90 degrees clockwise, and so on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Orientation {
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+    LeftTop,
+    RightTop,
+    RightBottom,
+    LeftBottom,
+    Other(u16),
+}
+
+impl From<u16> for Orientation {
+    fn from(code: u16) -> Orientation {
+        match code {
+            1 => Orientation::TopLeft,
+            2 => Orientation::TopRight,
+            3 => Orientation::BottomRight,
+            4 => Orientation::BottomLeft,
+            5 => Orientation::LeftTop,
+            6 => Orientation::RightTop,
+            7 => Orientation::RightBottom,
+            8 => Orientation::LeftBottom,
+            code => Orientation::Other(code),
+        }
+    }
+}
+
+// How the samples of each pixel are stored
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlanarConfig {
+    // Samples of each pixel together, as in RGBRGB
+    Chunky,
+    // Each sample in a plane of its own, as in RR GG BB
+    Planar,
+    Other(u16),
+}
+
+impl From<u16> for PlanarConfig {
+    fn from(code: u16) -> PlanarConfig {
+        match code {
+            1 => PlanarConfig::Chunky,
+            2 => PlanarConfig::Planar,
+            code => PlanarConfig::Other(code),
+        }
+    }
+}
+
+// What an image is to the others in the file, from NewSubFileType, whose TIFF bits DNG extends
+// with codes of its own
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubFileType {
+    // Full resolution image, the raw data in DNG
+    Main,
+    // Reduced resolution version of another image: thumbnails and previews
+    Preview,
+    // DNG, a preview rendered differently from the main one, say in another color space
+    AlternatePreview,
+    // DNG 1.2, transparency of the main image
+    TransparencyMask,
+    ReducedTransparencyMask,
+    // DNG 1.5
+    DepthMap,
+    ReducedDepthMap,
+    // DNG 1.6, the main image processed further, such as demosaiced or denoised
+    EnhancedImage,
+    // DNG 1.6, regions of the main image, such as skin or sky
+    SemanticMask,
+    Other(u32),
+}
+
+impl From<u32> for SubFileType {
+    fn from(code: u32) -> SubFileType {
+        match code {
+            0 => SubFileType::Main,
+            1 => SubFileType::Preview,
+            0x10001 => SubFileType::AlternatePreview,
+            4 => SubFileType::TransparencyMask,
+            5 => SubFileType::ReducedTransparencyMask,
+            8 => SubFileType::DepthMap,
+            9 => SubFileType::ReducedDepthMap,
+            16 => SubFileType::EnhancedImage,
+            0x10004 => SubFileType::SemanticMask,
+            code => SubFileType::Other(code),
+        }
+    }
+}
+
+impl Ifd {
+    /// Compression of the image data, None if the tag is missing, in which case the specs say
+    /// the image is uncompressed
+    #[must_use]
+    pub fn compression(&self) -> Option<Compression> {
+        self.code(Tag::Compression).map(Compression::from)
+    }
+
+    /// Color space of the image data, None if the tag is missing
+    #[must_use]
+    pub fn photometric(&self) -> Option<Photometric> {
+        self.code(Tag::PhotometricInterpretation)
+            .map(Photometric::from)
+    }
+
+    /// How to turn the image for display, None if the tag is missing, which means `TopLeft`
+    #[must_use]
+    pub fn orientation(&self) -> Option<Orientation> {
+        self.code(Tag::Orientation).map(Orientation::from)
+    }
+
+    /// How samples are stored, None if the tag is missing, which means `Chunky`
+    #[must_use]
+    pub fn planar_config(&self) -> Option<PlanarConfig> {
+        self.code(Tag::PlanarConfiguration).map(PlanarConfig::from)
+    }
+
+    /// What the image is to the others in the file, None if the tag is missing, which means
+    /// `Main`
+    #[must_use]
+    pub fn sub_file_type(&self) -> Option<SubFileType> {
+        self.number(Tag::NewSubFileType).map(SubFileType::from)
+    }
+
+    fn code(&self, tag: Tag) -> Option<u16> {
+        u16::try_from(self.number(tag)?).ok()
+    }
+}
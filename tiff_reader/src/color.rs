@@ -0,0 +1,178 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::ops::Mul;
+
+// Just enough linear algebra for DNG color processing, which never goes beyond 3x3 matrices and
+// 3 component vectors, so there is no need for a linear algebra crate.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec3(pub [f64; 3]);
+
+// Rows first, as DNG stores its matrices
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix3(pub [[f64; 3]; 3]);
+
+// 𝐅𝐫𝐨𝐦 𝐃𝐢𝐠𝐢𝐭𝐚𝐥 𝐍𝐞𝐠𝐚𝐭𝐢𝐯𝐞 𝐒𝐩𝐞𝐜𝐢𝐟𝐢𝐜𝐚𝐭𝐢𝐨𝐧, 𝐕𝐞𝐫𝐬𝐢𝐨𝐧 𝟏.𝟒.𝟎.𝟎, 𝐩𝐚𝐠𝐞 𝟖𝟒
+//
+// 𝑇ℎ𝑒 𝑝𝑟𝑜𝑓𝑖𝑙𝑒 𝑐𝑜𝑛𝑛𝑒𝑐𝑡𝑖𝑜𝑛 𝑠𝑝𝑎𝑐𝑒 (𝑃𝐶𝑆) 𝑖𝑠 𝑡ℎ𝑒 𝐶𝐼𝐸 1931 𝑋𝑌𝑍 𝑐𝑜𝑙𝑜𝑟 𝑠𝑝𝑎𝑐𝑒 𝑤𝑖𝑡ℎ 𝑎 𝐷50 𝑤ℎ𝑖𝑡𝑒 𝑝𝑜𝑖𝑛𝑡.
+impl Vec3 {
+    /// XYZ of the D50 white point, that of the DNG profile connection space
+    pub const D50: Vec3 = Vec3([0.9642, 1.0, 0.8249]);
+
+    /// XYZ of the D65 white point, that of sRGB
+    pub const D65: Vec3 = Vec3([0.9504, 1.0, 1.0888]);
+
+    /// XYZ, with Y = 1, of the given xy chromaticity coordinates, as in `AsShotWhiteXY`
+    #[must_use]
+    pub fn from_xy(x: f64, y: f64) -> Vec3 {
+        Vec3([x / y, 1.0, (1.0 - x - y) / y])
+    }
+}
+
+impl Matrix3 {
+    pub const IDENTITY: Matrix3 = Matrix3([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+    // Cone response domain used by the Bradford transform
+    const BRADFORD: Matrix3 = Matrix3([
+        [0.8951, 0.2664, -0.1614],
+        [-0.7502, 1.7135, 0.0367],
+        [0.0389, -0.0685, 1.0296],
+    ]);
+
+    /// Matrix whose diagonal is the given vector and everything else zero
+    #[must_use]
+    pub fn diagonal(vector: Vec3) -> Matrix3 {
+        let [x, y, z]: [f64; 3] = vector.0;
+        Matrix3([[x, 0.0, 0.0], [0.0, y, 0.0], [0.0, 0.0, z]])
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Matrix3 {
+        let rows: &[[f64; 3]; 3] = &self.0;
+        Matrix3(std::array::from_fn(|i| std::array::from_fn(|j| rows[j][i])))
+    }
+
+    #[must_use]
+    pub fn determinant(&self) -> f64 {
+        let rows: &[[f64; 3]; 3] = &self.0;
+        rows[0][0] * (rows[1][1] * rows[2][2] - rows[1][2] * rows[2][1])
+            - rows[0][1] * (rows[1][0] * rows[2][2] - rows[1][2] * rows[2][0])
+            + rows[0][2] * (rows[1][0] * rows[2][1] - rows[1][1] * rows[2][0])
+    }
+
+    /// None when the matrix is singular, or so close to it the inverse would be meaningless
+    #[must_use]
+    pub fn inverse(&self) -> Option<Matrix3> {
+        let determinant: f64 = self.determinant();
+        if !determinant.is_normal() {
+            return None;
+        }
+
+        // Transposed matrix of cofactors, divided by the determinant
+        let rows: &[[f64; 3]; 3] = &self.0;
+        Some(Matrix3(std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                let (row_a, row_b): (usize, usize) = ((j + 1) % 3, (j + 2) % 3);
+                let (column_a, column_b): (usize, usize) = ((i + 1) % 3, (i + 2) % 3);
+                (rows[row_a][column_a] * rows[row_b][column_b]
+                    - rows[row_a][column_b] * rows[row_b][column_a])
+                    / determinant
+            })
+        })))
+    }
+
+    /// Bradford chromatic adaptation, mapping XYZ colors seen under the source white point to
+    /// how they look under the destination one. None if either white point is black.
+    #[must_use]
+    pub fn bradford(source: Vec3, destination: Vec3) -> Option<Matrix3> {
+        let source_cone: Vec3 = Matrix3::BRADFORD * source;
+        let destination_cone: Vec3 = Matrix3::BRADFORD * destination;
+        let scale: Matrix3 = Matrix3::diagonal(Vec3(std::array::from_fn(|i| {
+            destination_cone.0[i] / source_cone.0[i]
+        })));
+        let adaptation: Matrix3 = Matrix3::BRADFORD.inverse()? * scale * Matrix3::BRADFORD;
+        adaptation
+            .0
+            .iter()
+            .flatten()
+            .all(|value| value.is_finite())
+            .then_some(adaptation)
+    }
+}
+
+impl From<[[f64; 3]; 3]> for Matrix3 {
+    fn from(rows: [[f64; 3]; 3]) -> Matrix3 {
+        Matrix3(rows)
+    }
+}
+
+impl Mul for Matrix3 {
+    type Output = Matrix3;
+
+    fn mul(self, other: Matrix3) -> Matrix3 {
+        Matrix3(std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..3).map(|k| self.0[i][k] * other.0[k][j]).sum())
+        }))
+    }
+}
+
+impl Mul<Vec3> for Matrix3 {
+    type Output = Vec3;
+
+    fn mul(self, vector: Vec3) -> Vec3 {
+        Vec3(std::array::from_fn(|i| {
+            (0..3).map(|k| self.0[i][k] * vector.0[k]).sum()
+        }))
+    }
+}
+
+// Chromaticities of light sources, given their correlated color temperature in kelvins, for
+// white balance set as a temperature and for interpolating between the two calibration
+// illuminants of dual illuminant profiles. Both formulas are only valid within the given ranges.
+
+/// xy chromaticity of CIE daylight, the D series illuminants, from 4000 K to 25000 K
+#[must_use]
+pub fn daylight_xy(temperature: f64) -> Option<(f64, f64)> {
+    let t: f64 = temperature;
+    let x: f64 = if (4000.0..=7000.0).contains(&t) {
+        -4.6070e9 / t.powi(3) + 2.9678e6 / t.powi(2) + 0.09911e3 / t + 0.244_063
+    } else if (7000.0..=25000.0).contains(&t) {
+        -2.0064e9 / t.powi(3) + 1.9018e6 / t.powi(2) + 0.24748e3 / t + 0.237_040
+    } else {
+        return None;
+    };
+    Some((x, -3.0 * x.powi(2) + 2.870 * x - 0.275))
+}
+
+/// xy chromaticity of a black body, the Planckian locus, from 1667 K to 25000 K. Uses the cubic
+/// spline approximation by Kim et al.
+#[must_use]
+pub fn planckian_xy(temperature: f64) -> Option<(f64, f64)> {
+    let t: f64 = temperature;
+    let x: f64 = if (1667.0..=4000.0).contains(&t) {
+        -0.266_123_9e9 / t.powi(3) - 0.234_358_9e6 / t.powi(2) + 0.877_695_6e3 / t + 0.179_910
+    } else if (4000.0..=25000.0).contains(&t) {
+        -3.025_846_9e9 / t.powi(3) + 2.107_037_9e6 / t.powi(2) + 0.222_634_7e3 / t + 0.240_390
+    } else {
+        return None;
+    };
+    let y: f64 = if t <= 2222.0 {
+        -1.106_381_4 * x.powi(3) - 1.348_110_20 * x.powi(2) + 2.185_558_32 * x - 0.202_196_83
+    } else if t <= 4000.0 {
+        -0.954_947_6 * x.powi(3) - 1.374_185_93 * x.powi(2) + 2.091_370_15 * x - 0.167_488_67
+    } else {
+        3.081_758_0 * x.powi(3) - 5.873_386_70 * x.powi(2) + 3.751_129_97 * x - 0.370_014_83
+    };
+    Some((x, y))
+}
@@ -0,0 +1,364 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::{Dng, Ifd, TiffError};
+use std::fmt::{Display, Formatter};
+
+// Date and time as Exif and TIFF store them, "YYYY:MM:DD HH:MM:SS", plus the fraction of a second
+// and offset from UTC Exif keeps in separate tags. Nothing is assumed about the time zone when
+// there is no offset: the camera clock is all there is.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    // Up to 60, for leap seconds
+    pub second: u8,
+    // From SubSecTime and the like, zero when there is none
+    pub nanosecond: u32,
+    // Minutes east of UTC, from OffsetTime and the like
+    pub offset: Option<i16>,
+}
+
+// What DateTime::parse_lenient() makes of text, with whether it was as the specs want it. Files in
+// the wild have dates without seconds, with slashes or dashes, or all zeros for unknown dates,
+// and the date and time there are is still worth having.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LenientDateTime {
+    // All zeros for the zero dates Exif allows when the date is unknown
+    pub date_time: DateTime,
+    // True only for text `DateTime::parse()` takes as it is
+    pub valid: bool,
+}
+
+impl DateTime {
+    /// Parses "YYYY:MM:DD HH:MM:SS", as in `DateTime`, or ISO 8601 "YYYY-MM-DDTHH:MM:SS", with
+    /// optional fraction and offset, as in `PreviewDateTime`
+    ///
+    /// # Errors
+    ///
+    /// Text in neither format, out of range values, and the blanks or zeros Exif allows for
+    /// unknown dates
+    pub fn parse(text: &str) -> Result<DateTime, TiffError> {
+        DateTime::parse_strict(text)
+            .ok_or_else(|| TiffError::InvalidData(format!("\"{text}\" is not a date and time")))
+    }
+
+    // What parse() does, without allocating an error for text that is not a date and time
+    fn parse_strict(text: &str) -> Option<DateTime> {
+        let bytes: &[u8] = text.trim_end_matches('\0').as_bytes();
+        let digits = |range: std::ops::Range<usize>| -> Option<u32> {
+            let digits: &[u8] = bytes.get(range)?;
+            if !digits.iter().all(u8::is_ascii_digit) {
+                return None;
+            }
+            Some(
+                digits
+                    .iter()
+                    .fold(0, |value, digit| value * 10 + u32::from(digit - b'0')),
+            )
+        };
+        let separators: [Option<u8>; 5] =
+            [4, 7, 10, 13, 16].map(|index: usize| bytes.get(index).copied());
+        if !matches!(
+            separators,
+            [Some(b':'), Some(b':'), Some(b' '), Some(b':'), Some(b':')]
+                | [Some(b'-'), Some(b'-'), Some(b'T'), Some(b':'), Some(b':')]
+        ) {
+            return None;
+        }
+
+        let date_time: DateTime = DateTime {
+            year: u16::try_from(digits(0..4)?).ok()?,
+            month: u8::try_from(digits(5..7)?).ok()?,
+            day: u8::try_from(digits(8..10)?).ok()?,
+            hour: u8::try_from(digits(11..13)?).ok()?,
+            minute: u8::try_from(digits(14..16)?).ok()?,
+            second: u8::try_from(digits(17..19)?).ok()?,
+            nanosecond: 0,
+            offset: None,
+        };
+        if !(1..=12).contains(&date_time.month)
+            || !(1..=31).contains(&date_time.day)
+            || date_time.hour > 23
+            || date_time.minute > 59
+            || date_time.second > 60
+        {
+            return None;
+        }
+
+        // Whatever ISO 8601 adds after the seconds
+        let mut rest: &str = text.trim_end_matches('\0').get(19..)?;
+        let mut date_time: DateTime = date_time;
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let length: usize = fraction
+                .find(|character: char| !character.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            date_time = date_time.with_subseconds(&fraction[..length]);
+            rest = &fraction[length..];
+        }
+        if !rest.is_empty() {
+            date_time = date_time.with_offset(rest)?;
+        }
+        Some(date_time)
+    }
+
+    /// Same as `parse()`, also taking the usual ways of getting it wrong: "/", "-" or "." between
+    /// date fields, single digit fields, missing seconds or time, fractions of a second, and
+    /// zero dates. Only ASCII digits count, whatever the locale. Needs no allocation.
+    #[must_use]
+    pub fn parse_lenient(text: &str) -> Option<LenientDateTime> {
+        if let Some(date_time) = DateTime::parse_strict(text) {
+            return Some(LenientDateTime {
+                date_time,
+                valid: true,
+            });
+        }
+        let text: &str = text
+            .trim_matches(|character: char| character == '\0' || character.is_ascii_whitespace());
+        let (date, time): (&str, &str) = text
+            .split_once([' ', 'T'])
+            .map_or((text, ""), |(date, time)| (date, time.trim_start()));
+
+        let mut fields = date.split([':', '-', '/', '.']);
+        let year: u32 = digits(fields.next()?, 4, 4)?;
+        let month: u32 = digits(fields.next()?, 1, 2)?;
+        let day: u32 = digits(fields.next()?, 1, 2)?;
+        if fields.next().is_some() {
+            return None;
+        }
+
+        // Fractions of a second only ever come after whole seconds
+        let (time, fraction): (&str, &str) = time.split_once('.').unwrap_or((time, ""));
+        let mut fields = time.split_terminator(':');
+        let hour: u32 = fields.next().map_or(Some(0), |field| digits(field, 1, 2))?;
+        let minute: u32 = fields.next().map_or(Some(0), |field| digits(field, 1, 2))?;
+        let second: u32 = fields.next().map_or(Some(0), |field| digits(field, 1, 2))?;
+        if fields.next().is_some() || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let date_time: DateTime = DateTime {
+            year: u16::try_from(year).ok()?,
+            month: u8::try_from(month).ok()?,
+            day: u8::try_from(day).ok()?,
+            hour: u8::try_from(hour).ok()?,
+            minute: u8::try_from(minute).ok()?,
+            second: u8::try_from(second).ok()?,
+            nanosecond: 0,
+            offset: None,
+        }
+        .with_subseconds(fraction);
+        let zero: bool = year == 0 && month == 0 && day == 0;
+        if !zero && (!(1..=12).contains(&month) || !(1..=31).contains(&day))
+            || hour > 23
+            || minute > 59
+            || second > 60
+        {
+            return None;
+        }
+        Some(LenientDateTime {
+            date_time,
+            valid: false,
+        })
+    }
+
+    // Digits of SubSecTime are a decimal fraction, however many there are. Blanks, which Exif
+    // allows for unknown digits, end it.
+    fn with_subseconds(mut self, digits: &str) -> DateTime {
+        self.nanosecond = digits
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .chain(std::iter::repeat(b'0'))
+            .take(9)
+            .fold(0, |value, digit| value * 10 + u32::from(digit - b'0'));
+        self
+    }
+
+    // "+HH:MM" or "-HH:MM", as in OffsetTime, or "Z" for UTC
+    fn with_offset(mut self, text: &str) -> Option<DateTime> {
+        let text: &str = text.trim_end_matches('\0');
+        if text == "Z" {
+            self.offset = Some(0);
+            return Some(self);
+        }
+        let (sign, hours_minutes): (i16, &str) = match text.split_at_checked(1)? {
+            ("+", rest) => (1, rest),
+            ("-", rest) => (-1, rest),
+            _ => return None,
+        };
+        let (hours, minutes): (&str, &str) = hours_minutes.split_once(':')?;
+        if hours.len() != 2 || minutes.len() != 2 {
+            return None;
+        }
+        let hours: i16 = hours.parse().ok().filter(|hours| *hours <= 14)?;
+        let minutes: i16 = minutes.parse().ok().filter(|minutes| *minutes <= 59)?;
+        self.offset = Some(sign * (hours * 60 + minutes));
+        Some(self)
+    }
+}
+
+impl Display for DateTime {
+    // ISO 8601
+    fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.nanosecond != 0 {
+            let fraction: String = format!("{:09}", self.nanosecond);
+            write!(formatter, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        match self.offset {
+            Some(0) => write!(formatter, "Z"),
+            Some(offset) => write!(
+                formatter,
+                "{}{:02}:{:02}",
+                if offset < 0 { '-' } else { '+' },
+                offset.unsigned_abs() / 60,
+                offset.unsigned_abs() % 60
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Dng {
+    /// When the file was last changed, from `DateTime` in IFD0, with `SubSecTime` and
+    /// `OffsetTime` from the Exif IFD when there
+    #[must_use]
+    pub fn date_time(&self) -> Option<DateTime> {
+        date_time(
+            &self.ifd0,
+            Tag::DateTime,
+            self.exif(),
+            Tag::SubSecTime,
+            Tag::OffsetTime,
+        )
+    }
+
+    /// When the picture was taken, from `DateTimeOriginal`, `SubSecTimeOriginal` and
+    /// `OffsetTimeOriginal` in the Exif IFD
+    #[must_use]
+    pub fn date_time_original(&self) -> Option<DateTime> {
+        let exif: &Ifd = self.exif()?;
+        date_time(
+            exif,
+            Tag::DateTimeOriginal,
+            Some(exif),
+            Tag::SubSecTimeOriginal,
+            Tag::OffsetTimeOriginal,
+        )
+    }
+
+    /// When the picture was stored as digital data, from `DateTimeDigitized`,
+    /// `SubSecTimeDigitized` and `OffsetTimeDigitized` in the Exif IFD
+    #[must_use]
+    pub fn date_time_digitized(&self) -> Option<DateTime> {
+        let exif: &Ifd = self.exif()?;
+        date_time(
+            exif,
+            Tag::DateTimeDigitized,
+            Some(exif),
+            Tag::SubSecTimeDigitized,
+            Tag::OffsetTimeDigitized,
+        )
+    }
+
+    /// When the preview was rendered, from `PreviewDateTime` in IFD0, which is ISO 8601 and
+    /// carries its own fraction and offset
+    #[must_use]
+    pub fn preview_date_time(&self) -> Option<DateTime> {
+        DateTime::parse(&self.ifd0.ascii(Tag::PreviewDateTime)?).ok()
+    }
+}
+
+// Between minimum and maximum ASCII digits, and nothing else
+fn digits(text: &str, minimum: usize, maximum: usize) -> Option<u32> {
+    if !(minimum..=maximum).contains(&text.len()) || !text.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return None;
+    }
+    Some(
+        text.bytes()
+            .fold(0, |value, digit| value * 10 + u32::from(digit - b'0')),
+    )
+}
+
+// Subseconds and offset only refine the date and time, so ones that make no sense are left out
+fn date_time(
+    ifd: &Ifd,
+    tag: Tag,
+    exif: Option<&Ifd>,
+    subseconds: Tag,
+    offset: Tag,
+) -> Option<DateTime> {
+    let mut date_time: DateTime = DateTime::parse(&ifd.ascii(tag)?).ok()?;
+    if let Some(digits) = exif.and_then(|exif| exif.ascii(subseconds)) {
+        date_time = date_time.with_subseconds(digits.trim_start());
+    }
+    if let Some(text) = exif.and_then(|exif| exif.ascii(offset)) {
+        date_time = date_time.with_offset(&text).unwrap_or(date_time);
+    }
+    Some(date_time)
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTime> for chrono::NaiveDateTime {
+    type Error = TiffError;
+
+    fn try_from(value: DateTime) -> Result<chrono::NaiveDateTime, TiffError> {
+        chrono::NaiveDate::from_ymd_opt(
+            i32::from(value.year),
+            u32::from(value.month),
+            u32::from(value.day),
+        )
+        .and_then(|date| {
+            // chrono keeps leap seconds as a nanosecond count past one second
+            let (second, nanosecond): (u8, u32) = if value.second == 60 {
+                (59, value.nanosecond + 1_000_000_000)
+            } else {
+                (value.second, value.nanosecond)
+            };
+            date.and_hms_nano_opt(
+                u32::from(value.hour),
+                u32::from(value.minute),
+                u32::from(second),
+                nanosecond,
+            )
+        })
+        .ok_or_else(|| TiffError::InvalidData(format!("{value} is not a valid date and time")))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+    type Error = TiffError;
+
+    /// Fails for date and times with no offset, whose time zone is unknown
+    fn try_from(value: DateTime) -> Result<chrono::DateTime<chrono::FixedOffset>, TiffError> {
+        let offset: chrono::FixedOffset = value
+            .offset
+            .and_then(|offset| chrono::FixedOffset::east_opt(i32::from(offset) * 60))
+            .ok_or_else(|| TiffError::InvalidData(format!("{value} has no offset from UTC")))?;
+        chrono::NaiveDateTime::try_from(value)?
+            .and_local_timezone(offset)
+            .single()
+            .ok_or_else(|| TiffError::InvalidData(format!("{value} is not a valid date and time")))
+    }
+}
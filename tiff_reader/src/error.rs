@@ -0,0 +1,266 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+use std::fmt::{Display, Formatter};
+use std::io::{Error, ErrorKind};
+
+// Everything that can go wrong reading a TIFF file, so callers can tell failures apart without
+// parsing messages. Where the field being read is known, it is reported along with the failure.
+#[derive(Debug)]
+pub enum TiffError {
+    // Failure of the underlying reader
+    Io(Error),
+    // Wrong byte order, magic number or first IFD offset
+    InvalidHeader(String),
+    // Offset that cannot be followed
+    BadOffset {
+        tag: Option<Tag>,
+        offset: u64,
+        reason: &'static str,
+    },
+    // Field whose type is not one its tag allows
+    UnexpectedType {
+        tag: Tag,
+        type_: u16,
+    },
+    // Field with more or fewer values than its tag allows
+    UnexpectedCount {
+        tag: Tag,
+        expected: u32,
+        found: usize,
+    },
+    // Value too large to even compute its size
+    ValueTooLarge {
+        tag: Option<Tag>,
+        type_: u16,
+        count: u32,
+    },
+    // Fewer bytes available than a value needs
+    TruncatedValue {
+        tag: Option<Tag>,
+        expected: usize,
+        found: usize,
+    },
+    // Same tag more than once in an IFD
+    DuplicateTag(Tag),
+    // Tag a caller asked for is not in the IFD
+    MissingTag(Tag),
+    // Any other violation of the TIFF or DNG specifications
+    InvalidData(String),
+    // Reading before the byte order is known
+    Uninitialized,
+    // File needs more than one of the limits in ReaderOptions allows
+    LimitExceeded {
+        limit: &'static str,
+        maximum: usize,
+    },
+}
+
+impl TiffError {
+    // Errors reading a value do not know the field it belongs to, the caller does
+    pub(crate) fn with_tag(self, tag: Tag) -> TiffError {
+        match self {
+            TiffError::BadOffset {
+                tag: None,
+                offset,
+                reason,
+            } => TiffError::BadOffset {
+                tag: Some(tag),
+                offset,
+                reason,
+            },
+            TiffError::TruncatedValue {
+                tag: None,
+                expected,
+                found,
+            } => TiffError::TruncatedValue {
+                tag: Some(tag),
+                expected,
+                found,
+            },
+            TiffError::ValueTooLarge {
+                tag: None,
+                type_,
+                count,
+            } => TiffError::ValueTooLarge {
+                tag: Some(tag),
+                type_,
+                count,
+            },
+            error => error,
+        }
+    }
+
+    // Field the error is about, where it says
+    pub(crate) fn tag(&self) -> Option<Tag> {
+        match self {
+            TiffError::BadOffset { tag, .. }
+            | TiffError::ValueTooLarge { tag, .. }
+            | TiffError::TruncatedValue { tag, .. } => *tag,
+            TiffError::UnexpectedType { tag, .. }
+            | TiffError::UnexpectedCount { tag, .. }
+            | TiffError::DuplicateTag(tag)
+            | TiffError::MissingTag(tag) => Some(*tag),
+            _ => None,
+        }
+    }
+
+    // Copy of the error, for reports that outlive the borrow of it. I/O errors keep only their
+    // kind and message, as io::Error cannot be cloned.
+    pub(crate) fn copy(&self) -> TiffError {
+        match self {
+            TiffError::Io(error) => TiffError::Io(Error::new(error.kind(), error.to_string())),
+            TiffError::InvalidHeader(message) => TiffError::InvalidHeader(message.clone()),
+            TiffError::BadOffset {
+                tag,
+                offset,
+                reason,
+            } => TiffError::BadOffset {
+                tag: *tag,
+                offset: *offset,
+                reason,
+            },
+            TiffError::UnexpectedType { tag, type_ } => TiffError::UnexpectedType {
+                tag: *tag,
+                type_: *type_,
+            },
+            TiffError::UnexpectedCount {
+                tag,
+                expected,
+                found,
+            } => TiffError::UnexpectedCount {
+                tag: *tag,
+                expected: *expected,
+                found: *found,
+            },
+            TiffError::ValueTooLarge { tag, type_, count } => TiffError::ValueTooLarge {
+                tag: *tag,
+                type_: *type_,
+                count: *count,
+            },
+            TiffError::TruncatedValue {
+                tag,
+                expected,
+                found,
+            } => TiffError::TruncatedValue {
+                tag: *tag,
+                expected: *expected,
+                found: *found,
+            },
+            TiffError::DuplicateTag(tag) => TiffError::DuplicateTag(*tag),
+            TiffError::MissingTag(tag) => TiffError::MissingTag(*tag),
+            TiffError::InvalidData(message) => TiffError::InvalidData(message.clone()),
+            TiffError::Uninitialized => TiffError::Uninitialized,
+            TiffError::LimitExceeded { limit, maximum } => TiffError::LimitExceeded {
+                limit,
+                maximum: *maximum,
+            },
+        }
+    }
+}
+
+impl Display for TiffError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TiffError::Io(error) => write!(formatter, "{error}"),
+            TiffError::InvalidHeader(message) | TiffError::InvalidData(message) => {
+                write!(formatter, "{message}")
+            }
+            TiffError::BadOffset {
+                tag: Some(tag),
+                offset,
+                reason,
+            } => write!(formatter, "\"{tag:?}\" offset {offset} {reason}"),
+            TiffError::BadOffset {
+                tag: None,
+                offset,
+                reason,
+            } => write!(formatter, "Offset {offset} {reason}"),
+            TiffError::DuplicateTag(tag) => {
+                write!(
+                    formatter,
+                    "\"{tag:?}\" appears more than once in the same IFD"
+                )
+            }
+            TiffError::MissingTag(tag) => write!(formatter, "\"{tag:?}\" is not in the IFD"),
+            TiffError::UnexpectedType { tag, type_ } => {
+                write!(formatter, "\"{tag:?}\" cannot have type {type_}")
+            }
+            TiffError::UnexpectedCount {
+                tag,
+                expected,
+                found,
+            } => write!(
+                formatter,
+                "\"{tag:?}\" should have {expected} values, found {found}"
+            ),
+            TiffError::ValueTooLarge { tag, type_, count } => {
+                if let Some(tag) = tag {
+                    write!(formatter, "\"{tag:?}\": ")?;
+                }
+                write!(
+                    formatter,
+                    "{count} values of type {type_} are too large to read"
+                )
+            }
+            TiffError::TruncatedValue {
+                tag,
+                expected,
+                found,
+            } => {
+                if let Some(tag) = tag {
+                    write!(formatter, "\"{tag:?}\": ")?;
+                }
+                write!(
+                    formatter,
+                    "Tried to read {expected} bytes, found only {found} bytes available"
+                )
+            }
+            TiffError::LimitExceeded { limit, maximum } => {
+                write!(formatter, "File exceeds {limit} limit of {maximum}")
+            }
+            TiffError::Uninitialized => write!(
+                formatter,
+                "TiffReader was not initialized correctly before read attempt"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TiffError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TiffError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for TiffError {
+    fn from(error: Error) -> TiffError {
+        TiffError::Io(error)
+    }
+}
+
+// For callers that deal in std::io::Error, as this crate did before TiffError
+impl From<TiffError> for Error {
+    fn from(error: TiffError) -> Error {
+        match error {
+            TiffError::Io(error) => error,
+            TiffError::TruncatedValue { .. } => Error::new(ErrorKind::UnexpectedEof, error),
+            TiffError::Uninitialized => Error::other(error),
+            _ => Error::new(ErrorKind::InvalidData, error),
+        }
+    }
+}
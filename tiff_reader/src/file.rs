@@ -0,0 +1,109 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use crate::{ReaderOptions, TiffError, TiffReader};
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+use std::fs::File;
+#[cfg(feature = "mmap")]
+use std::io::Cursor;
+use std::io::{BufReader, Error, Read, Seek, SeekFrom};
+use std::path::Path;
+
+// Files at least this large are memory mapped, when possible
+#[cfg(feature = "mmap")]
+const MMAP_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+// How TiffReader::open() reads files
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Backend {
+    // Memory maps large files, if the "mmap" feature is enabled, and buffers the rest
+    #[default]
+    Auto,
+    Buffered,
+    #[cfg(feature = "mmap")]
+    Mapped,
+}
+
+// Reader TiffReader::open() picks for a file
+pub enum FileSource {
+    Buffered(BufReader<File>),
+    #[cfg(feature = "mmap")]
+    Mapped(Cursor<Mmap>),
+}
+
+impl FileSource {
+    fn open(path: &Path, backend: Backend) -> Result<FileSource, Error> {
+        let file: File = File::open(path)?;
+        match backend {
+            #[cfg(feature = "mmap")]
+            Backend::Mapped => map(&file),
+            #[cfg(feature = "mmap")]
+            Backend::Auto if file.metadata()?.len() >= MMAP_THRESHOLD => {
+                // Some files, such as those in special file systems, cannot be mapped
+                map(&file).or_else(|_| Ok(FileSource::Buffered(BufReader::new(file))))
+            }
+            _ => Ok(FileSource::Buffered(BufReader::new(file))),
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+fn map(file: &File) -> Result<FileSource, Error> {
+    // Undefined behavior if the file is changed while mapped, a risk every memory mapping reader
+    // takes. Files being read are not expected to be written to at the same time.
+    let mmap: Mmap = unsafe { Mmap::map(file)? };
+    Ok(FileSource::Mapped(Cursor::new(mmap)))
+}
+
+impl Read for FileSource {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        match self {
+            FileSource::Buffered(reader) => reader.read(buffer),
+            #[cfg(feature = "mmap")]
+            FileSource::Mapped(reader) => reader.read(buffer),
+        }
+    }
+}
+
+impl Seek for FileSource {
+    fn seek(&mut self, position: SeekFrom) -> Result<u64, Error> {
+        match self {
+            FileSource::Buffered(reader) => reader.seek(position),
+            #[cfg(feature = "mmap")]
+            FileSource::Mapped(reader) => reader.seek(position),
+        }
+    }
+}
+
+impl TiffReader<FileSource> {
+    /// Opens the file at path with the reader best suited to it, see `ReaderOptions::backend()`
+    ///
+    /// # Errors
+    ///
+    /// Failure to open the file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<TiffReader<FileSource>, TiffError> {
+        TiffReader::open_with_options(path, ReaderOptions::default())
+    }
+
+    /// # Errors
+    ///
+    /// Failure to open the file
+    pub fn open_with_options<P: AsRef<Path>>(
+        path: P,
+        options: ReaderOptions,
+    ) -> Result<TiffReader<FileSource>, TiffError> {
+        let source: FileSource = FileSource::open(path.as_ref(), options.backend)?;
+        TiffReader::with_options(source, options)
+    }
+}
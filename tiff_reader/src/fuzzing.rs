@@ -0,0 +1,66 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use crate::{Dng, Ifd, Matrix3, ReaderOptions, TiffError, TiffReader};
+use std::io::Cursor;
+
+// Bounds how much work arbitrary input can cause, a few IFDs are enough to reach every code path
+const MAX_IFDS: usize = 256;
+// Values cannot point past the end of the input, but many fields can point at the same bytes
+const MAX_READ_FACTOR: usize = 16;
+
+/// Runs bytes through the whole parser, for property based tests and fuzzers: walks the IFD chain
+/// loading every value on demand, then reads it again as a DNG. Reading is lenient and bounded
+/// by limits, so any input, however malformed, ends in a result rather than a panic, unbounded
+/// allocation or runaway recursion.
+///
+/// # Errors
+///
+/// Whatever `TiffReader::read_dng()` makes of bytes
+pub fn parse_arbitrary_bytes(bytes: &[u8]) -> Result<Dng, TiffError> {
+    let options: ReaderOptions = ReaderOptions::new()
+        .strict(false)
+        .max_ifds(MAX_IFDS)
+        .max_total_bytes(bytes.len().saturating_mul(MAX_READ_FACTOR));
+
+    let mut tiff_reader: TiffReader<Cursor<&[u8]>> =
+        TiffReader::with_options(Cursor::new(bytes), options.clone().lazy(true))?;
+    let ifds: Vec<Ifd> = tiff_reader.ifds()?.map_while(Result::ok).collect();
+    for mut ifd in ifds {
+        load_all(&mut ifd, &mut tiff_reader);
+    }
+
+    TiffReader::with_options(Cursor::new(bytes), options)?.read_dng()
+}
+
+// Errors loading one value say nothing about the others, so they are ignored
+fn load_all(ifd: &mut Ifd, tiff_reader: &mut TiffReader<Cursor<&[u8]>>) {
+    for field in ifd.fields.values_mut() {
+        if field.load(tiff_reader).is_ok() {
+            let _: String = field.to_string_lossy();
+            let _: Option<Matrix3> = field.as_matrix3x3();
+        }
+    }
+    for (_, field) in &mut ifd.duplicates {
+        let _: Result<(), TiffError> = field.load(tiff_reader);
+    }
+    let children = ifd
+        .sub_ifds
+        .iter_mut()
+        .chain(ifd.exif.as_deref_mut())
+        .chain(ifd.gps.as_deref_mut())
+        .chain(ifd.interoperability.as_deref_mut());
+    for child in children {
+        load_all(child, tiff_reader);
+    }
+}
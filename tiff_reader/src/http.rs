@@ -0,0 +1,162 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
+use ureq::{Agent, Response};
+
+// Reading a raw file's metadata, or even its previews, needs a tiny part of it. HttpRangeReader
+// provides Read and Seek over HTTP byte range requests, so that is all that gets downloaded from
+// object storage and the like. Data is fetched in fixed size blocks, kept for later reads, and
+// each read asks for all blocks it is missing in a single request.
+pub struct HttpRangeReader {
+    agent: Agent,
+    url: String,
+    length: u64,
+    position: u64,
+    block_size: u64,
+    // Blocks fetched so far, by index
+    blocks: HashMap<u64, Vec<u8>>,
+}
+
+impl HttpRangeReader {
+    /// # Errors
+    ///
+    /// Failure to reach the server, or a response without the length of the file
+    pub fn new(url: &str) -> Result<HttpRangeReader, Error> {
+        let agent: Agent = Agent::new();
+        let response: Response = agent.head(url).call().map_err(Error::other)?;
+        let length: u64 = response
+            .header("Content-Length")
+            .and_then(|length| length.parse().ok())
+            .ok_or_else(|| Error::other(format!("No Content-Length for {url}")))?;
+        Ok(HttpRangeReader {
+            agent,
+            url: url.to_string(),
+            length,
+            position: 0,
+            block_size: 64 * 1024,
+            blocks: HashMap::new(),
+        })
+    }
+
+    /// Bytes fetched at a time, 64 KiB by default. Larger blocks mean fewer requests, but more
+    /// data downloaded for nothing.
+    #[must_use]
+    pub fn block_size(mut self, block_size: u64) -> HttpRangeReader {
+        self.block_size = block_size.max(1);
+        self.blocks.clear();
+        self
+    }
+
+    // Fetches blocks first to last, inclusive, in a single request
+    fn fetch(&mut self, first: u64, last: u64) -> Result<(), Error> {
+        let start: u64 = first * self.block_size;
+        let end: u64 = ((last + 1) * self.block_size).min(self.length);
+        let response: Response = self
+            .agent
+            .get(&self.url)
+            .set("Range", &format!("bytes={start}-{}", end - 1))
+            .call()
+            .map_err(Error::other)?;
+        // Servers that ignore ranges answer 200 with the whole file
+        if response.status() != 206 {
+            return Err(Error::other(format!(
+                "{} does not support range requests",
+                self.url
+            )));
+        }
+
+        let mut data: Vec<u8> = Vec::new();
+        response.into_reader().read_to_end(&mut data)?;
+        if u64::try_from(data.len()).ok() != Some(end - start) {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                format!("Asked {} for bytes {start} to {end}", self.url),
+            ));
+        }
+        for (index, block) in (first..=last).zip(data.chunks(self.chunk_size())) {
+            self.blocks.insert(index, block.to_vec());
+        }
+        Ok(())
+    }
+
+    fn chunk_size(&self) -> usize {
+        usize::try_from(self.block_size).unwrap_or(usize::MAX)
+    }
+}
+
+impl Read for HttpRangeReader {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        let end: u64 = self
+            .position
+            .saturating_add(u64::try_from(buffer.len()).unwrap_or(u64::MAX))
+            .min(self.length);
+        if self.position >= end {
+            return Ok(0);
+        }
+
+        // Fetches each run of missing blocks with a single request
+        let first: u64 = self.position / self.block_size;
+        let last: u64 = (end - 1) / self.block_size;
+        let mut index: u64 = first;
+        while index <= last {
+            if self.blocks.contains_key(&index) {
+                index += 1;
+                continue;
+            }
+            let mut run_end: u64 = index;
+            while run_end < last && !self.blocks.contains_key(&(run_end + 1)) {
+                run_end += 1;
+            }
+            self.fetch(index, run_end)?;
+            index = run_end + 1;
+        }
+
+        let mut bytes_read: usize = 0;
+        while self.position < end {
+            let block: &[u8] = self
+                .blocks
+                .get(&(self.position / self.block_size))
+                .and_then(|block| {
+                    block.get(usize::try_from(self.position % self.block_size).ok()?..)
+                })
+                .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "Missing block"))?;
+            let count: usize = block.len().min(buffer.len() - bytes_read);
+            if count == 0 {
+                break;
+            }
+            buffer[bytes_read..bytes_read + count].copy_from_slice(&block[..count]);
+            bytes_read += count;
+            self.position += u64::try_from(count).unwrap_or(u64::MAX);
+        }
+        Ok(bytes_read)
+    }
+}
+
+impl Seek for HttpRangeReader {
+    fn seek(&mut self, position: SeekFrom) -> Result<u64, Error> {
+        let (base, delta): (u64, i64) = match position {
+            SeekFrom::Start(offset) => (offset, 0),
+            SeekFrom::Current(delta) => (self.position, delta),
+            SeekFrom::End(delta) => (self.length, delta),
+        };
+        self.position = base.checked_add_signed(delta).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "Invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
@@ -0,0 +1,213 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::{Field, Ifd, TiffError, Vec3};
+
+// ICC profile, as embedded in InterColorProfile. Only the little needed to tell profiles apart is
+// decoded; the bytes are kept as they are, so rendered output can embed the same profile.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IccProfile {
+    pub header: IccHeader,
+    // From the "desc" tag, in English if there is a choice
+    pub description: Option<String>,
+    // Media white point, from the "wtpt" tag
+    pub white_point: Option<Vec3>,
+    bytes: Vec<u8>,
+}
+
+// Fixed size block every ICC profile starts with, big endian as everything else in them
+#[derive(Clone, Debug, PartialEq)]
+pub struct IccHeader {
+    pub size: u32,
+    // Signatures are four characters, such as "mntr" for the device class of displays
+    pub cmm: String,
+    // Major, minor and bug fix
+    pub version: (u8, u8, u8),
+    pub device_class: String,
+    pub color_space: String,
+    pub connection_space: String,
+    pub rendering_intent: u32,
+    // Illuminant of the profile connection space, D50 in practice
+    pub illuminant: Vec3,
+    pub creator: String,
+}
+
+const HEADER_SIZE: usize = 128;
+
+impl IccProfile {
+    /// Decodes the header, description and white point of an ICC profile
+    ///
+    /// # Errors
+    ///
+    /// If bytes are not an ICC profile, or tags they point to are out of bounds
+    pub fn parse(bytes: &[u8]) -> Result<IccProfile, TiffError> {
+        if bytes.len() < HEADER_SIZE || &bytes[36..40] != b"acsp" {
+            return Err(TiffError::InvalidData(
+                "ICC profile header is missing its \"acsp\" signature".to_string(),
+            ));
+        }
+
+        let header: IccHeader = IccHeader {
+            size: u32_at(bytes, 0)?,
+            cmm: signature(bytes, 4)?,
+            version: (bytes[8], bytes[9] >> 4, bytes[9] & 0x0f),
+            device_class: signature(bytes, 12)?,
+            color_space: signature(bytes, 16)?,
+            connection_space: signature(bytes, 20)?,
+            rendering_intent: u32_at(bytes, 64)?,
+            illuminant: xyz_at(bytes, 68)?,
+            creator: signature(bytes, 80)?,
+        };
+
+        let mut description: Option<String> = None;
+        let mut white_point: Option<Vec3> = None;
+        let tag_count: usize = usize_at(bytes, HEADER_SIZE)?;
+        for index in 0..tag_count {
+            let entry: usize = HEADER_SIZE + 4 + index.saturating_mul(12);
+            if entry + 12 > bytes.len() {
+                return Err(TiffError::InvalidData(format!(
+                    "ICC profile claims {tag_count} tags, but has room for only {index}"
+                )));
+            }
+            let data: &[u8] = slice(
+                bytes,
+                usize_at(bytes, entry + 4)?,
+                usize_at(bytes, entry + 8)?,
+            )?;
+            match &bytes[entry..entry + 4] {
+                b"desc" => description = text(data)?,
+                b"wtpt" => white_point = Some(xyz_at(data, 8)?),
+                _ => {}
+            }
+        }
+
+        Ok(IccProfile {
+            header,
+            description,
+            white_point,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Profile as found in the file, ready to embed elsewhere
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Ifd {
+    /// ICC profile describing the colors of this IFD's image, if there is one
+    ///
+    /// # Errors
+    ///
+    /// If `InterColorProfile` is not a byte array, or not an ICC profile
+    pub fn icc_profile(&self) -> Result<Option<IccProfile>, TiffError> {
+        match self.fields.get(&Tag::InterColorProfile) {
+            None => Ok(None),
+            Some(Field::Byte(bytes) | Field::Undefined(bytes)) => {
+                IccProfile::parse(bytes).map(Some)
+            }
+            Some(_) => Err(TiffError::InvalidData(
+                "\"InterColorProfile\" is not a byte array, or was not loaded".to_string(),
+            )),
+        }
+    }
+}
+
+// Description, either as textDescriptionType, up to version 2, or multiLocalizedUnicodeType
+fn text(data: &[u8]) -> Result<Option<String>, TiffError> {
+    match data.get(0..4) {
+        Some(b"desc") => {
+            let length: usize = usize_at(data, 8)?;
+            let ascii: &[u8] = slice(data, 12, length)?;
+            Ok(Some(
+                String::from_utf8_lossy(ascii)
+                    .trim_end_matches('\0')
+                    .to_string(),
+            ))
+        }
+        Some(b"mluc") => {
+            let records: usize = usize_at(data, 8)?;
+            let record_size: usize = usize_at(data, 12)?;
+            let mut chosen: Option<usize> = None;
+            for index in 0..records {
+                let record: usize = index.saturating_mul(record_size).saturating_add(16);
+                if record.saturating_add(12) > data.len() {
+                    break;
+                }
+                if &data[record..record + 2] == b"en" {
+                    chosen = Some(record);
+                    break;
+                }
+                chosen.get_or_insert(record);
+            }
+            let Some(record) = chosen else {
+                return Ok(None);
+            };
+            let utf16: Vec<u16> = slice(
+                data,
+                usize_at(data, record + 8)?,
+                usize_at(data, record + 4)?,
+            )?
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+            Ok(Some(String::from_utf16_lossy(&utf16)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn slice(bytes: &[u8], offset: usize, size: usize) -> Result<&[u8], TiffError> {
+    let end: usize = offset.saturating_add(size);
+    bytes.get(offset..end).ok_or(TiffError::TruncatedValue {
+        tag: Some(Tag::InterColorProfile),
+        expected: end,
+        found: bytes.len(),
+    })
+}
+
+// ICC profiles are always big endian, whatever the byte order of the file around them
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, TiffError> {
+    Ok(u32::from_be_bytes(array_at(bytes, offset)?))
+}
+
+// Offsets and sizes, for indexing
+fn usize_at(bytes: &[u8], offset: usize) -> Result<usize, TiffError> {
+    Ok(usize::try_from(u32_at(bytes, offset)?).unwrap_or(usize::MAX))
+}
+
+fn array_at(bytes: &[u8], offset: usize) -> Result<[u8; 4], TiffError> {
+    let mut buffer: [u8; 4] = [0; 4];
+    buffer.copy_from_slice(slice(bytes, offset, 4)?);
+    Ok(buffer)
+}
+
+fn signature(bytes: &[u8], offset: usize) -> Result<String, TiffError> {
+    Ok(String::from_utf8_lossy(slice(bytes, offset, 4)?)
+        .trim_end_matches([' ', '\0'])
+        .to_string())
+}
+
+// XYZNumber, three s15Fixed16Number values
+fn xyz_at(bytes: &[u8], offset: usize) -> Result<Vec3, TiffError> {
+    let mut xyz: [f64; 3] = [0.0; 3];
+    for (index, value) in xyz.iter_mut().enumerate() {
+        let fixed: i32 = i32::from_be_bytes(array_at(bytes, offset + index * 4)?);
+        *value = f64::from(fixed) / 65536.0;
+    }
+    Ok(Vec3(xyz))
+}
@@ -0,0 +1,182 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::{Field, Ifd, TiffError};
+
+// Editorial metadata, as news agencies and asset management tools write it: IPTC-NAA datasets,
+// found either in IPTCNAA or among the Photoshop image resources. Only the most used datasets of
+// the application record are decoded; all of them are kept, as they are, in datasets.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Iptc {
+    // 2:05
+    pub object_name: Option<String>,
+    // 2:25, which may repeat
+    pub keywords: Vec<String>,
+    // 2:80
+    pub by_line: Option<String>,
+    // 2:105
+    pub headline: Option<String>,
+    // 2:110
+    pub credit: Option<String>,
+    // 2:116
+    pub copyright_notice: Option<String>,
+    // 2:120
+    pub caption: Option<String>,
+    // Record, dataset number and data, in file order
+    pub datasets: Vec<(u8, u8, Vec<u8>)>,
+}
+
+// Photoshop image resource holding IPTC-NAA datasets
+const IPTC_RESOURCE: u16 = 0x0404;
+
+impl Iptc {
+    /// Decodes a sequence of IPTC-NAA datasets. Padding after the last one is ignored.
+    ///
+    /// # Errors
+    ///
+    /// If a dataset claims more bytes than there are
+    pub fn parse(bytes: &[u8]) -> Result<Iptc, TiffError> {
+        let mut iptc: Iptc = Iptc::default();
+        let mut position: usize = 0;
+
+        // Each dataset is a 0x1C tag marker, record and dataset numbers and a big endian length,
+        // followed by the data. Lengths with the high bit set give instead the size of the
+        // actual length, which follows.
+        while bytes.get(position) == Some(&0x1c) && position + 5 <= bytes.len() {
+            let record: u8 = bytes[position + 1];
+            let dataset: u8 = bytes[position + 2];
+            let mut length: usize = usize::from(u16::from_be_bytes([
+                bytes[position + 3],
+                bytes[position + 4],
+            ]));
+            position += 5;
+            if length & 0x8000 != 0 {
+                let size: usize = length & 0x7fff;
+                length = take(bytes, position, size)?
+                    .iter()
+                    .fold(0usize, |length, byte| {
+                        length
+                            .saturating_mul(256)
+                            .saturating_add(usize::from(*byte))
+                    });
+                position += size;
+            }
+            let data: &[u8] = take(bytes, position, length)?;
+            position += length;
+
+            if record == 2 {
+                let text: String = String::from_utf8_lossy(data)
+                    .trim_end_matches('\0')
+                    .to_string();
+                match dataset {
+                    5 => iptc.object_name = Some(text),
+                    25 => iptc.keywords.push(text),
+                    80 => iptc.by_line = Some(text),
+                    105 => iptc.headline = Some(text),
+                    110 => iptc.credit = Some(text),
+                    116 => iptc.copyright_notice = Some(text),
+                    120 => iptc.caption = Some(text),
+                    _ => {}
+                }
+            }
+            iptc.datasets.push((record, dataset, data.to_vec()));
+        }
+
+        Ok(iptc)
+    }
+}
+
+/// Photoshop image resources: identifier, name and data of each, in file order
+///
+/// # Errors
+///
+/// If a resource claims more bytes than there are
+pub fn photoshop_resources(bytes: &[u8]) -> Result<Vec<(u16, String, Vec<u8>)>, TiffError> {
+    let mut resources: Vec<(u16, String, Vec<u8>)> = Vec::new();
+    let mut position: usize = 0;
+
+    // Each resource is the "8BIM" signature, a big endian identifier, a Pascal string name and a
+    // big endian size, followed by the data. Names and data are padded to an even size.
+    while bytes.get(position..position + 4) == Some(b"8BIM") && position + 7 <= bytes.len() {
+        let id: u16 = u16::from_be_bytes([bytes[position + 4], bytes[position + 5]]);
+        let name_length: usize = usize::from(bytes[position + 6]);
+        let name: &[u8] = take(bytes, position + 7, name_length)?;
+        position += 6 + (1 + name_length).next_multiple_of(2);
+        let size: usize = take(bytes, position, 4)?
+            .iter()
+            .fold(0usize, |size, byte| (size << 8) | usize::from(*byte));
+        let data: &[u8] = take(bytes, position + 4, size)?;
+        position += 4 + size.next_multiple_of(2);
+        resources.push((id, String::from_utf8_lossy(name).to_string(), data.to_vec()));
+    }
+
+    Ok(resources)
+}
+
+impl Ifd {
+    /// IPTC-NAA metadata, from `IPTCNAA` or, failing that, from the Photoshop image resources
+    ///
+    /// # Errors
+    ///
+    /// If either field is not a byte array, or holds malformed data
+    pub fn iptc(&self) -> Result<Option<Iptc>, TiffError> {
+        if let Some(field) = self.fields.get(&Tag::IPTCNAA) {
+            return Iptc::parse(&bytes(Tag::IPTCNAA, field)?).map(Some);
+        }
+        if let Some(field) = self.fields.get(&Tag::Photoshop) {
+            let resources: Vec<(u16, String, Vec<u8>)> =
+                photoshop_resources(&bytes(Tag::Photoshop, field)?)?;
+            if let Some((_, _, data)) = resources.iter().find(|(id, _, _)| *id == IPTC_RESOURCE) {
+                return Iptc::parse(data).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+// IPTCNAA is LONG as often as not, which loses the byte order of the file. Datasets start with
+// 0x1C, so whichever order puts it first is the right one.
+fn bytes(tag: Tag, field: &Field) -> Result<Vec<u8>, TiffError> {
+    match field {
+        Field::Byte(bytes) | Field::Undefined(bytes) => Ok(bytes.clone()),
+        Field::Long(values) => {
+            let little_endian: bool = values
+                .first()
+                .is_some_and(|value| value.to_le_bytes()[0] == 0x1c);
+            Ok(values
+                .iter()
+                .flat_map(|value| {
+                    if little_endian {
+                        value.to_le_bytes()
+                    } else {
+                        value.to_be_bytes()
+                    }
+                })
+                .collect())
+        }
+        _ => Err(TiffError::InvalidData(format!(
+            "\"{tag:?}\" is not a byte array, or was not loaded"
+        ))),
+    }
+}
+
+fn take(bytes: &[u8], offset: usize, size: usize) -> Result<&[u8], TiffError> {
+    let end: usize = offset.saturating_add(size);
+    bytes.get(offset..end).ok_or(TiffError::TruncatedValue {
+        tag: None,
+        expected: end,
+        found: bytes.len(),
+    })
+}
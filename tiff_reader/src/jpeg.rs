@@ -0,0 +1,226 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::{Dng, Field, Ifd, TiffError, TiffReader};
+use std::io::{Read, Seek};
+
+// What the frame header of a JPEG stream says about the image, found without decoding any of it.
+// Only markers and segment lengths are read, up to the first SOF segment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JpegInfo {
+    // SOF marker, 0xC0 to 0xCF: baseline, progressive, lossless and so on
+    pub marker: u8,
+    // Bits per sample
+    pub precision: u8,
+    // Zero when the height is only given by a DNL segment after the first scan
+    pub height: u16,
+    pub width: u16,
+    pub components: Vec<JpegComponent>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JpegComponent {
+    pub id: u8,
+    // Sampling factors, 1 to 4
+    pub horizontal: u8,
+    pub vertical: u8,
+}
+
+// An image other than the full resolution raw one, as its tags describe it and, for JPEG
+// compressed ones, as its own JPEG stream does
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preview {
+    // Where the image is, named as in ImageStorage
+    pub name: String,
+    pub compression: u32,
+    pub width: u32,
+    pub length: u32,
+    // Offset and size of the image data, for previews stored as a single strip
+    pub offset: Option<u64>,
+    pub bytes: Option<u64>,
+    pub jpeg: Option<JpegInfo>,
+}
+
+impl JpegInfo {
+    /// Frame header of a JPEG stream. Only needs the bytes up to the end of the first SOF segment.
+    ///
+    /// # Errors
+    ///
+    /// Bytes that do not start with SOI, or end before a frame header is found
+    pub fn parse(bytes: &[u8]) -> Result<JpegInfo, TiffError> {
+        if bytes.get(0..2) != Some(&[0xFF, 0xD8]) {
+            return Err(TiffError::InvalidData(
+                "JPEG stream does not start with SOI".to_string(),
+            ));
+        }
+        let mut position: usize = 2;
+        loop {
+            // Any number of 0xFF fill bytes may come before a marker
+            if bytes.get(position) != Some(&0xFF) {
+                return Err(truncated("marker expected"));
+            }
+            while bytes.get(position) == Some(&0xFF) {
+                position += 1;
+            }
+            let marker: u8 = *bytes
+                .get(position)
+                .ok_or_else(|| truncated("no frame header"))?;
+            position += 1;
+            match marker {
+                // SOF markers, the rest of 0xC0 to 0xCF are DHT, JPG and DAC
+                0xC0..=0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => {
+                    return JpegInfo::parse_frame(marker, bytes.get(position..).unwrap_or(&[]));
+                }
+                0xDA | 0xD9 => return Err(truncated("scan before frame header")),
+                // Markers that stand alone, with no segment after them
+                0x01 | 0xD0..=0xD8 => {}
+                _ => {
+                    let length: usize = usize::from(be_u16(bytes, position)?);
+                    position = position.saturating_add(length);
+                }
+            }
+        }
+    }
+
+    // Frame header after its marker: length, precision, height, width and components
+    fn parse_frame(marker: u8, bytes: &[u8]) -> Result<JpegInfo, TiffError> {
+        let count: usize = usize::from(*bytes.get(7).ok_or_else(|| truncated("frame header"))?);
+        let components: Vec<JpegComponent> = bytes
+            .get(8..8 + 3 * count)
+            .ok_or_else(|| truncated("frame header"))?
+            .chunks_exact(3)
+            .map(|component| JpegComponent {
+                id: component[0],
+                horizontal: component[1] >> 4,
+                vertical: component[1] & 0x0F,
+            })
+            .collect();
+        Ok(JpegInfo {
+            marker,
+            precision: bytes[2],
+            height: be_u16(bytes, 3)?,
+            width: be_u16(bytes, 5)?,
+            components,
+        })
+    }
+
+    /// Coding process the SOF marker stands for, leaving out whether it is differential and how
+    /// it is entropy coded
+    #[must_use]
+    pub fn process(&self) -> &'static str {
+        match self.marker & 0x03 {
+            0 => "baseline",
+            1 => "sequential",
+            2 => "progressive",
+            _ => "lossless",
+        }
+    }
+
+    /// Chroma subsampling in the usual J:a:b notation, for three component images whose second
+    /// and third components are sampled alike. None for anything else.
+    #[must_use]
+    pub fn subsampling(&self) -> Option<&'static str> {
+        let [luma, cb, cr]: [JpegComponent; 3] = self.components.clone().try_into().ok()?;
+        if (cb.horizontal, cb.vertical) != (cr.horizontal, cr.vertical)
+            || cb.horizontal == 0
+            || cb.vertical == 0
+        {
+            return None;
+        }
+        match (luma.horizontal / cb.horizontal, luma.vertical / cb.vertical) {
+            (1, 1) => Some("4:4:4"),
+            (1, 2) => Some("4:4:0"),
+            (2, 1) => Some("4:2:2"),
+            (2, 2) => Some("4:2:0"),
+            (4, 1) => Some("4:1:1"),
+            _ => None,
+        }
+    }
+}
+
+impl<R: Read + Seek> TiffReader<R> {
+    /// Every image of the DNG but the full resolution raw one. The frame headers of JPEG
+    /// compressed previews stored as a single strip are read too, as Preview tags and even
+    /// `ImageWidth` and `ImageLength` do not always tell the truth about them. Nothing is decoded,
+    /// and only the first 64 KiB of each strip are read. Strips that cannot be read and JPEG
+    /// streams that cannot be made sense of only leave jpeg empty.
+    pub fn previews(&mut self, dng: &Dng) -> Vec<Preview> {
+        let mut previews: Vec<Preview> = Vec::new();
+        for (name, ifd, raw) in dng.images() {
+            if raw {
+                continue;
+            }
+            let (Some(width), Some(length)) =
+                (ifd.number(Tag::ImageWidth), ifd.number(Tag::ImageLength))
+            else {
+                continue;
+            };
+            let compression: u32 = ifd.number(Tag::Compression).unwrap_or(1);
+            let strip: Option<(u64, u64)> = single_strip(ifd);
+            let mut jpeg: Option<JpegInfo> = None;
+            // 6 is old-style JPEG, 7 JPEG as in TIFF Technical Note 2, 34892 lossy JPEG as in DNG
+            if let (Some((offset, bytes)), 6 | 7 | 34892) = (strip, compression) {
+                let length: usize =
+                    usize::try_from(bytes).map_or(JPEG_PREFIX, |bytes| bytes.min(JPEG_PREFIX));
+                jpeg = self
+                    .read_bytes(offset, length)
+                    .and_then(|prefix| JpegInfo::parse(&prefix))
+                    .ok();
+            }
+            previews.push(Preview {
+                name,
+                compression,
+                width,
+                length,
+                offset: strip.map(|(offset, _)| offset),
+                bytes: strip.map(|(_, bytes)| bytes),
+                jpeg,
+            });
+        }
+        previews
+    }
+}
+
+// Bytes of a preview read looking for its frame header. Only APPn segments, at most 64 KiB each,
+// usually come before it, and Exif and the like take just one.
+const JPEG_PREFIX: usize = 64 * 1024;
+
+// Offset and byte count of an image stored as a single strip
+fn single_strip(ifd: &Ifd) -> Option<(u64, u64)> {
+    let offset: u64 = single_u64(ifd.fields.get(&Tag::StripOffsets)?)?;
+    let bytes: u64 = single_u64(ifd.fields.get(&Tag::StripByteCounts)?)?;
+    Some((offset, bytes))
+}
+
+fn single_u64(field: &Field) -> Option<u64> {
+    match field {
+        Field::Short(values) if values.len() == 1 => Some(u64::from(values[0])),
+        Field::Long(values) if values.len() == 1 => Some(u64::from(values[0])),
+        Field::Long8(values) if values.len() == 1 => Some(values[0]),
+        _ => None,
+    }
+}
+
+// JPEG is always big endian
+fn be_u16(bytes: &[u8], offset: usize) -> Result<u16, TiffError> {
+    match bytes.get(offset..offset.saturating_add(2)) {
+        Some(&[high, low]) => Ok(u16::from_be_bytes([high, low])),
+        _ => Err(truncated("segment length")),
+    }
+}
+
+fn truncated(what: &str) -> TiffError {
+    TiffError::InvalidData(format!("Malformed JPEG stream: {what}"))
+}
@@ -12,12 +12,75 @@
 // 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
 
 use data::{
-    type_size, Byte, Double, Float, Long, Sbyte, Short, Slong, Sshort, Tag, Undefined, ASCII,
-    DOUBLE, FLOAT, LONG, RATIONAL, SBYTE, SHORT, SLONG, SRATIONAL, SSHORT,
+    type_size, Byte, Double, Float, Long, Long8, Sbyte, Short, Slong, Slong8, Sshort, Tag,
+    Undefined, ASCII, BYTE, DOUBLE, FLOAT, IFD, IFD8, LONG, LONG8, RATIONAL, SBYTE, SHORT, SLONG,
+    SLONG8, SRATIONAL, SSHORT, UNDEFINED, UTF8,
 };
 use num_rational::Ratio;
-use std::collections::HashMap;
+use std::collections::HashSet;
+#[cfg(feature = "btree")]
+use std::collections::{btree_map, BTreeMap};
+#[cfg(not(feature = "btree"))]
+use std::collections::{hash_map, HashMap};
+use std::fmt::{Display, Formatter};
 use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+#[cfg(feature = "tokio")]
+mod async_reader;
+mod black;
+mod builder;
+mod canon;
+mod capabilities;
+mod codes;
+mod color;
+mod datetime;
+mod error;
+mod file;
+#[cfg(feature = "fuzzing")]
+mod fuzzing;
+#[cfg(feature = "http")]
+mod http;
+mod icc;
+mod iptc;
+mod jpeg;
+mod lookup;
+mod options;
+mod rational;
+mod raw_image;
+mod segments;
+#[cfg(feature = "serde")]
+mod serialize;
+mod spool;
+mod storage;
+mod typed;
+mod validate;
+#[cfg(feature = "tokio")]
+pub use async_reader::read_dng_async;
+pub use black::BlackLevels;
+pub use builder::IfdBuilder;
+pub use canon::{CameraSettings, CanonMakerNote, ShotInfo};
+pub use capabilities::{capabilities, Capabilities};
+pub use codes::{Compression, Orientation, Photometric, PlanarConfig, SubFileType};
+pub use color::{daylight_xy, planckian_xy, Matrix3, Vec3};
+pub use datetime::{DateTime, LenientDateTime};
+pub use error::TiffError;
+pub use file::{Backend, FileSource};
+#[cfg(feature = "fuzzing")]
+pub use fuzzing::parse_arbitrary_bytes;
+#[cfg(feature = "http")]
+pub use http::HttpRangeReader;
+pub use icc::{IccHeader, IccProfile};
+pub use iptc::{photoshop_resources, Iptc};
+pub use jpeg::{JpegComponent, JpegInfo, Preview};
+pub use options::{DuplicateTags, OddOffsets, ReaderOptions};
+pub use rational::{Rational, SRational};
+pub use raw_image::RawImage;
+pub use segments::Segment;
+pub use spool::Spool;
+pub use storage::{ImageStorage, StorageReport};
+pub use typed::TypedField;
+pub use validate::{Issue, Severity, ValidationReport};
 
 // 𝐅𝐫𝐨𝐦 𝐃𝐢𝐠𝐢𝐭𝐚𝐥 𝐍𝐞𝐠𝐚𝐭𝐢𝐯𝐞 𝐒𝐩𝐞𝐜𝐢𝐟𝐢𝐜𝐚𝐭𝐢𝐨𝐧, 𝐕𝐞𝐫𝐬𝐢𝐨𝐧 𝟏.𝟒.𝟎.𝟎, 𝐩𝐚𝐠𝐞 𝟏𝟑
 //
@@ -40,17 +103,386 @@ use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
 pub struct Dng {
     pub ifd0: Ifd,
     pub hires_ifd: Ifd,
+    // Position of hires_ifd in SubIFDs. The other SubIFDs stay in ifd0.sub_ifds, in file order.
+    pub hires_index: usize,
+    // Spec violations let through in lenient mode while reading the file
+    pub warnings: Vec<TiffError>,
 }
 
+impl Dng {
+    /// Exif private IFD, from IFD0
+    #[must_use]
+    pub fn exif(&self) -> Option<&Ifd> {
+        self.ifd0.exif.as_deref()
+    }
+
+    /// GPS IFD, from IFD0
+    #[must_use]
+    pub fn gps(&self) -> Option<&Ifd> {
+        self.ifd0.gps.as_deref()
+    }
+
+    /// Interoperability IFD, from the Exif IFD
+    #[must_use]
+    pub fn interoperability(&self) -> Option<&Ifd> {
+        self.exif()?.interoperability.as_deref()
+    }
+
+    /// Every image of the given type: IFD0, the IFDs its `SubIFDs` point to and theirs, in file
+    /// order
+    #[must_use]
+    pub fn sub_ifds_of_type(&self, sub_file_type: SubFileType) -> Vec<&Ifd> {
+        fn collect<'a>(ifd: &'a Ifd, sub_file_type: SubFileType, found: &mut Vec<&'a Ifd>) {
+            if ifd.sub_file_type().unwrap_or(SubFileType::Main) == sub_file_type {
+                found.push(ifd);
+            }
+            for sub_ifd in &ifd.sub_ifds {
+                collect(sub_ifd, sub_file_type, found);
+            }
+        }
+
+        let mut found: Vec<&Ifd> = Vec::new();
+        if self.ifd0.sub_file_type().unwrap_or(SubFileType::Main) == sub_file_type {
+            found.push(&self.ifd0);
+        }
+        // hires_ifd back where it was taken out from
+        let mut sub_ifds: Vec<&Ifd> = self.ifd0.sub_ifds.iter().collect();
+        sub_ifds.insert(self.hires_index.min(sub_ifds.len()), &self.hires_ifd);
+        for sub_ifd in sub_ifds {
+            collect(sub_ifd, sub_file_type, &mut found);
+        }
+        found
+    }
+
+    /// Thumbnail and previews, reduced resolution renderings of the raw image, IFD0 usually
+    /// being the first one
+    #[must_use]
+    pub fn preview_ifds(&self) -> Vec<&Ifd> {
+        let mut previews: Vec<&Ifd> = self.sub_ifds_of_type(SubFileType::Preview);
+        previews.extend(self.sub_ifds_of_type(SubFileType::AlternatePreview));
+        previews
+    }
+
+    /// Enhanced image, DNG 1.6
+    #[must_use]
+    pub fn enhanced_ifd(&self) -> Option<&Ifd> {
+        self.sub_ifds_of_type(SubFileType::EnhancedImage)
+            .into_iter()
+            .next()
+    }
+
+    /// Full resolution transparency mask, DNG 1.2
+    #[must_use]
+    pub fn transparency_mask(&self) -> Option<&Ifd> {
+        self.sub_ifds_of_type(SubFileType::TransparencyMask)
+            .into_iter()
+            .next()
+    }
+
+    /// Full resolution depth map, DNG 1.5
+    #[must_use]
+    pub fn depth_map(&self) -> Option<&Ifd> {
+        self.sub_ifds_of_type(SubFileType::DepthMap)
+            .into_iter()
+            .next()
+    }
+
+    /// Semantic masks, DNG 1.6
+    #[must_use]
+    pub fn semantic_masks(&self) -> Vec<&Ifd> {
+        self.sub_ifds_of_type(SubFileType::SemanticMask)
+    }
+
+    /// Latitude in decimal degrees, negative south of the Equator
+    #[must_use]
+    pub fn latitude(&self) -> Option<f64> {
+        let gps: &Ifd = self.gps()?;
+        let degrees: f64 = gps.degrees(Tag::GPSLatitude)?;
+        match gps.ascii(Tag::GPSLatitudeRef)?.as_str() {
+            "N" => Some(degrees),
+            "S" => Some(-degrees),
+            _ => None,
+        }
+    }
+
+    /// Longitude in decimal degrees, negative west of Greenwich
+    #[must_use]
+    pub fn longitude(&self) -> Option<f64> {
+        let gps: &Ifd = self.gps()?;
+        let degrees: f64 = gps.degrees(Tag::GPSLongitude)?;
+        match gps.ascii(Tag::GPSLongitudeRef)?.as_str() {
+            "E" => Some(degrees),
+            "W" => Some(-degrees),
+            _ => None,
+        }
+    }
+
+    /// Altitude in meters, negative below sea level
+    #[must_use]
+    pub fn altitude(&self) -> Option<f64> {
+        let gps: &Ifd = self.gps()?;
+        let altitude: f64 = match gps.fields.get(&Tag::GPSAltitude)? {
+            Field::Rational(values) => to_f64(*values.first()?)?,
+            _ => return None,
+        };
+        // GPSAltitudeRef is optional, and 0 (above sea level) by default
+        match gps.fields.get(&Tag::GPSAltitudeRef) {
+            Some(Field::Byte(reference)) if reference.first() == Some(&1) => Some(-altitude),
+            _ => Some(altitude),
+        }
+    }
+
+    /// Camera information, gathered from wherever DNG stores it
+    #[must_use]
+    pub fn camera(&self) -> Camera {
+        let compression: Option<Compression> = self.hires_ifd.compression();
+        let photometric: Option<Photometric> = self.hires_ifd.photometric();
+
+        Camera {
+            make: self.ifd0.ascii(Tag::Make),
+            model: self.ifd0.ascii(Tag::Model),
+            unique_model: self.ifd0.ascii(Tag::UniqueCameraModel),
+            serial_number: self.ifd0.ascii(Tag::CameraSerialNumber),
+            // Name and version of the camera firmware, in files straight from the camera, or of
+            // the converter that wrote the DNG otherwise
+            firmware: self.ifd0.ascii(Tag::Software),
+            width: self.hires_ifd.number(Tag::ImageWidth),
+            length: self.hires_ifd.number(Tag::ImageLength),
+            cfa_pattern: self.cfa_pattern(),
+            // Digital Negative Specification, Version 1.4.0.0, pages 19 and 20
+            decode_path: match (compression, photometric) {
+                (Some(Compression::Uncompressed), Some(Photometric::Cfa)) => {
+                    DecodePath::UncompressedCfa
+                }
+                (Some(Compression::Uncompressed), Some(Photometric::LinearRaw)) => {
+                    DecodePath::UncompressedLinear
+                }
+                _ => DecodePath::Unsupported,
+            },
+        }
+    }
+
+    /// Color filter array of the raw image, None if it has none or the tags describing it are
+    /// inconsistent
+    #[must_use]
+    pub fn cfa_pattern(&self) -> Option<CfaPattern> {
+        let ifd: &Ifd = &self.hires_ifd;
+        let (rows, columns): (Short, Short) = match ifd.fields.get(&Tag::CFARepeatPatternDim)? {
+            Field::Short(dim) if dim.len() == 2 => (dim[0], dim[1]),
+            _ => return None,
+        };
+        let colors: Vec<Byte> = match ifd.fields.get(&Tag::CFAPattern)? {
+            Field::Byte(colors) | Field::Undefined(colors) => colors.clone(),
+            _ => return None,
+        };
+
+        // 𝐅𝐫𝐨𝐦 𝐃𝐢𝐠𝐢𝐭𝐚𝐥 𝐍𝐞𝐠𝐚𝐭𝐢𝐯𝐞 𝐒𝐩𝐞𝐜𝐢𝐟𝐢𝐜𝐚𝐭𝐢𝐨𝐧, 𝐕𝐞𝐫𝐬𝐢𝐨𝐧 𝟏.𝟒.𝟎.𝟎
+        //
+        // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 0, 1, 2 (𝑟𝑒𝑑, 𝑔𝑟𝑒𝑒𝑛, 𝑏𝑙𝑢𝑒)
+        let plane_colors: Vec<Byte> = match ifd.fields.get(&Tag::CFAPlaneColor) {
+            Some(Field::Byte(plane_colors)) => plane_colors.clone(),
+            _ => vec![0, 1, 2],
+        };
+
+        CfaPattern::new(
+            rows,
+            columns,
+            colors,
+            plane_colors,
+            // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 1 (𝑟𝑒𝑐𝑡𝑎𝑛𝑔𝑢𝑙𝑎𝑟)
+            ifd.number(Tag::CFALayout).unwrap_or(1),
+        )
+    }
+}
+
+// Camera information is spread over several tags and IFDs, and most of it is optional. This puts
+// it in one place, so applications can display it consistently.
+#[derive(Debug)]
+pub struct Camera {
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub unique_model: Option<String>,
+    pub serial_number: Option<String>,
+    pub firmware: Option<String>,
+    pub width: Option<u32>,
+    pub length: Option<u32>,
+    pub cfa_pattern: Option<CfaPattern>,
+    pub decode_path: DecodePath,
+}
+
+// Arrangement of color filters over the sensor, decoded from CFARepeatPatternDim, CFAPattern,
+// CFAPlaneColor and CFALayout. Colors use the TIFF/EP codes: 0 red, 1 green, 2 blue, 3 cyan,
+// 4 magenta, 5 yellow, 6 white.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CfaPattern {
+    pub rows: Short,
+    pub columns: Short,
+    // Color of each position in the repeating pattern, rows first
+    pub colors: Vec<Byte>,
+    // Color of each plane in the linear image
+    pub plane_colors: Vec<Byte>,
+    // 1 for a rectangular grid, other values for the staggered layouts DNG defines
+    pub layout: u32,
+}
+
+impl CfaPattern {
+    /// None unless colors fill the rows by columns pattern, with at least one of each
+    #[must_use]
+    pub fn new(
+        rows: Short,
+        columns: Short,
+        colors: Vec<Byte>,
+        plane_colors: Vec<Byte>,
+        layout: u32,
+    ) -> Option<CfaPattern> {
+        if rows == 0 || columns == 0 || colors.len() != usize::from(rows) * usize::from(columns) {
+            return None;
+        }
+        Some(CfaPattern {
+            rows,
+            columns,
+            colors,
+            plane_colors,
+            layout,
+        })
+    }
+
+    /// Color of the filter over the given pixel of the raw image, None for patterns whose
+    /// dimensions do not match their colors, which `CfaPattern::new()` never returns
+    #[must_use]
+    pub fn color_at(&self, row: usize, column: usize) -> Option<Byte> {
+        let columns: usize = usize::from(self.columns);
+        let index: usize = (row.checked_rem(usize::from(self.rows))?)
+            .checked_mul(columns)?
+            .checked_add(column.checked_rem(columns)?)?;
+        self.colors.get(index).copied()
+    }
+
+    /// Plane of the linear image the given pixel of the raw image belongs to, None if its color is
+    /// not one of the planes
+    #[must_use]
+    pub fn plane_at(&self, row: usize, column: usize) -> Option<usize> {
+        let color: Byte = self.color_at(row, column)?;
+        self.plane_colors.iter().position(|plane| *plane == color)
+    }
+}
+
+// The usual way to name patterns, such as "RGGB"
+impl Display for CfaPattern {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        for color in &self.colors {
+            match color {
+                0 => write!(formatter, "R")?,
+                1 => write!(formatter, "G")?,
+                2 => write!(formatter, "B")?,
+                3 => write!(formatter, "C")?,
+                4 => write!(formatter, "M")?,
+                5 => write!(formatter, "Y")?,
+                6 => write!(formatter, "W")?,
+                _ => write!(formatter, "?")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// How, if at all, this crate can decode the raw image data
+#[derive(Debug)]
+pub enum DecodePath {
+    UncompressedCfa,
+    UncompressedLinear,
+    Unsupported,
+}
+
+// Fields are kept in a HashMap by default. Enabling feature "btree" stores them in a BTreeMap
+// instead, so iteration is sorted by tag number and deterministic, at the cost of slower lookups.
+#[cfg(not(feature = "btree"))]
+pub type Fields = HashMap<Tag, Field>;
+#[cfg(feature = "btree")]
+pub type Fields = BTreeMap<Tag, Field>;
+#[cfg(not(feature = "btree"))]
+pub type FieldEntry<'a> = hash_map::Entry<'a, Tag, Field>;
+#[cfg(feature = "btree")]
+pub type FieldEntry<'a> = btree_map::Entry<'a, Tag, Field>;
+
 pub struct Ifd {
-    pub fields: HashMap<Tag, Field>,
+    pub fields: Fields,
     pub offset: u64,
+    // Where the IFD starts in the file, 0 for IFDs built rather than read
+    pub position: u64,
+    // IFDs pointed to by the SubIFDs field, in the order they appear there
+    pub sub_ifds: Vec<Ifd>,
+    // Exif private IFD, pointed to by the ExifIFD field
+    pub exif: Option<Box<Ifd>>,
+    // GPS IFD, pointed to by the GPSInfo field
+    pub gps: Option<Box<Ifd>>,
+    // Interoperability IFD, pointed to by the InteroperabilityIFD field of the Exif IFD
+    pub interoperability: Option<Box<Ifd>>,
+    // Fields for tags found more than once, other than the first, when keeping all of them
+    pub duplicates: Vec<(Tag, Field)>,
+}
+
+impl Ifd {
+    /// Field for the tag, if there is one
+    #[must_use]
+    pub fn get(&self, tag: Tag) -> Option<&Field> {
+        self.fields.get(&tag)
+    }
+
+    /// Field for the tag, if there is one, to change in place
+    pub fn get_mut(&mut self, tag: Tag) -> Option<&mut Field> {
+        self.fields.get_mut(&tag)
+    }
+
+    /// Sets the field for the tag, returning the one it replaces, if any
+    pub fn insert(&mut self, tag: Tag, field: Field) -> Option<Field> {
+        self.fields.insert(tag, field)
+    }
+
+    /// Takes the field for the tag out of the IFD, if there is one
+    pub fn remove(&mut self, tag: Tag) -> Option<Field> {
+        self.fields.remove(&tag)
+    }
+
+    /// Field for the tag, present or not, for changes that depend on which
+    pub fn entry(&mut self, tag: Tag) -> FieldEntry<'_> {
+        self.fields.entry(tag)
+    }
+
+    // Exif 3.0 files may store text as UTF-8 instead of ASCII, either is fine
+    fn ascii(&self, tag: Tag) -> Option<String> {
+        match self.fields.get(&tag) {
+            Some(Field::Ascii(value, _) | Field::Utf8(value, _)) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    // GPS coordinates are stored as three RATIONALs: degrees, minutes and seconds
+    fn degrees(&self, tag: Tag) -> Option<f64> {
+        match self.fields.get(&tag)? {
+            Field::Rational(values) if values.len() == 3 => {
+                Some(to_f64(values[0])? + to_f64(values[1])? / 60.0 + to_f64(values[2])? / 3600.0)
+            }
+            _ => None,
+        }
+    }
+
+    // TIFF allows most numeric tags to be either SHORT or LONG
+    fn number(&self, tag: Tag) -> Option<u32> {
+        match self.fields.get(&tag)? {
+            Field::Short(values) => values.first().map(|value| u32::from(*value)),
+            Field::Long(values) => values.first().copied(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum Field {
     Byte(Vec<Byte>),
-    Ascii(String),
+    // Text and the count in the file, which also has the NUL terminator and any padding
+    Ascii(String, u32),
+    Utf8(String, u32),
     Short(Vec<Short>),
     Long(Vec<Long>),
     Rational(Vec<Ratio<Long>>),
@@ -61,96 +493,430 @@ pub enum Field {
     Srational(Vec<Ratio<Slong>>),
     Float(Vec<Float>),
     Double(Vec<Double>),
+    // Offsets to child IFDs
+    Ifd(Vec<Long>),
+    Long8(Vec<Long8>),
+    Slong8(Vec<Slong8>),
+    Ifd8(Vec<Long8>),
+    // Value not read yet, see ReaderOptions::lazy()
+    Lazy { type_: u16, count: u32, offset: u64 },
+}
+
+impl Field {
+    /// Reads the value of a lazily loaded field, replacing it in place. Fields already loaded are
+    /// left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Those caused by the underlying reader
+    pub fn load<R: Read + Seek>(&mut self, reader: &mut TiffReader<R>) -> Result<(), TiffError> {
+        if let Field::Lazy {
+            type_,
+            count,
+            offset,
+        } = *self
+        {
+            *self = reader.load_field(type_, count, offset)?;
+        }
+        Ok(())
+    }
+
+    /// Strings of an ASCII or UTF-8 field. ASCII fields may hold several, each ending with a NUL,
+    /// which are split here. None for fields of any other type.
+    #[must_use]
+    pub fn strings(&self) -> Option<Vec<String>> {
+        match self {
+            Field::Ascii(value, _) | Field::Utf8(value, _) => {
+                Some(value.split('\0').map(str::to_string).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Value as text: strings as they are, several of them separated by NULs, bytes decoded as
+    /// UTF-8, replacing invalid sequences, and numbers separated by spaces, rationals as fractions.
+    #[must_use]
+    pub fn to_string_lossy(&self) -> String {
+        fn join<T: ToString>(values: &[T]) -> String {
+            values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(" ")
+        }
+
+        match self {
+            Field::Ascii(value, _) | Field::Utf8(value, _) => value.clone(),
+            Field::Byte(bytes) | Field::Undefined(bytes) => {
+                let length: usize = bytes
+                    .iter()
+                    .rposition(|byte| *byte != 0)
+                    .map_or(0, |i| i + 1);
+                String::from_utf8_lossy(&bytes[..length]).into_owned()
+            }
+            Field::Short(values) => join(values),
+            Field::Long(values) | Field::Ifd(values) => join(values),
+            Field::Rational(values) => join(values),
+            Field::Sbyte(values) => join(values),
+            Field::Sshort(values) => join(values),
+            Field::Slong(values) => join(values),
+            Field::Srational(values) => join(values),
+            Field::Float(values) => join(values),
+            Field::Double(values) => join(values),
+            Field::Long8(values) | Field::Ifd8(values) => join(values),
+            Field::Slong8(values) => join(values),
+            Field::Lazy { .. } => String::new(),
+        }
+    }
+
+    /// Values as a 3x3 matrix in row major order, as DNG stores `ColorMatrix1` and the like
+    #[must_use]
+    pub fn as_matrix3x3(&self) -> Option<Matrix3> {
+        let values: Vec<f64> = self.to_f64s()?;
+        if values.len() != 9 {
+            return None;
+        }
+        Some(Matrix3([
+            [values[0], values[1], values[2]],
+            [values[3], values[4], values[5]],
+            [values[6], values[7], values[8]],
+        ]))
+    }
+
+    /// Value of a numeric field with a single value, as an integer. Rationals qualify when they
+    /// are whole numbers.
+    ///
+    /// # Errors
+    ///
+    /// Fields that are not numbers or are not loaded, have more or fewer than one value, or hold
+    /// a rational that is not a whole number
+    pub fn as_scalar_i64(&self) -> Result<i64, TiffError> {
+        match self {
+            Field::Byte(values) => scalar(values).map(i64::from),
+            Field::Short(values) => scalar(values).map(i64::from),
+            Field::Long(values) | Field::Ifd(values) => scalar(values).map(i64::from),
+            Field::Sbyte(values) => scalar(values).map(i64::from),
+            Field::Sshort(values) => scalar(values).map(i64::from),
+            Field::Slong(values) => scalar(values).map(i64::from),
+            Field::Slong8(values) => scalar(values),
+            Field::Long8(values) | Field::Ifd8(values) => scalar(values).and_then(|value| {
+                i64::try_from(value).map_err(|_| {
+                    TiffError::InvalidData(format!("{value} is too large for a signed integer"))
+                })
+            }),
+            Field::Rational(values) => scalar(values)
+                .and_then(|value| whole(i64::from(*value.numer()), i64::from(*value.denom()))),
+            Field::Srational(values) => scalar(values)
+                .and_then(|value| whole(i64::from(*value.numer()), i64::from(*value.denom()))),
+            Field::Float(_) | Field::Double(_) => Err(TiffError::InvalidData(
+                "Floating point values are not integers, see Field::as_scalar_f64()".to_string(),
+            )),
+            _ => Err(not_a_number(self)),
+        }
+    }
+
+    /// Value of a numeric field with a single value, of any type, as a float
+    ///
+    /// # Errors
+    ///
+    /// Fields that are not numbers or are not loaded, have more or fewer than one value, or hold
+    /// a rational dividing by zero
+    pub fn as_scalar_f64(&self) -> Result<f64, TiffError> {
+        match self {
+            Field::Float(values) => scalar(values).map(f64::from),
+            Field::Double(values) => scalar(values),
+            Field::Rational(values) => scalar(values).and_then(|value| {
+                to_f64(value).ok_or_else(|| {
+                    TiffError::InvalidData(format!("Rational {value} divides by zero"))
+                })
+            }),
+            Field::Srational(values) => scalar(values).and_then(|value| {
+                to_f64(value).ok_or_else(|| {
+                    TiffError::InvalidData(format!("Rational {value} divides by zero"))
+                })
+            }),
+            Field::Byte(values) => scalar(values).map(f64::from),
+            Field::Short(values) => scalar(values).map(f64::from),
+            Field::Long(values) => scalar(values).map(f64::from),
+            Field::Sbyte(values) => scalar(values).map(f64::from),
+            Field::Sshort(values) => scalar(values).map(f64::from),
+            Field::Slong(values) => scalar(values).map(f64::from),
+            Field::Long8(values) => scalar(values).map(u64_to_f64),
+            Field::Slong8(values) => scalar(values).map(i64_to_f64),
+            _ => Err(not_a_number(self)),
+        }
+    }
+
+    // Any kind of number as f64, None if there are non numbers, or rationals dividing by zero
+    fn to_f64s(&self) -> Option<Vec<f64>> {
+        match self {
+            Field::Short(values) => Some(values.iter().map(|value| f64::from(*value)).collect()),
+            Field::Long(values) => Some(values.iter().map(|value| f64::from(*value)).collect()),
+            Field::Sshort(values) => Some(values.iter().map(|value| f64::from(*value)).collect()),
+            Field::Slong(values) => Some(values.iter().map(|value| f64::from(*value)).collect()),
+            Field::Long8(values) => Some(values.iter().copied().map(u64_to_f64).collect()),
+            Field::Slong8(values) => Some(values.iter().copied().map(i64_to_f64).collect()),
+            Field::Float(values) => Some(values.iter().map(|value| f64::from(*value)).collect()),
+            Field::Double(values) => Some(values.clone()),
+            Field::Rational(values) => values.iter().map(|value| to_f64(*value)).collect(),
+            Field::Srational(values) => values.iter().map(|value| to_f64(*value)).collect(),
+            _ => None,
+        }
+    }
 }
 
 pub struct TiffReader<R> {
     reader: R,
     endianness: Endianness,
+    header: Option<Header>,
+    options: ReaderOptions,
+    // Spec violations let through in lenient mode
+    warnings: Vec<TiffError>,
+    // Size of the file, known once the header is read, for offsets to be checked against
+    length: u64,
+    // Counted against the limits in options
+    ifds_read: usize,
+    bytes_read: usize,
 }
 
+pub struct Ifds<'a, R> {
+    tiff_reader: &'a mut TiffReader<R>,
+    // Offset of the next IFD to read; 0 once the chain is over
+    offset: u64,
+    // Offsets of the IFDs read so far, a next IFD offset pointing back at one of them would
+    // otherwise loop forever
+    visited: Vec<u64>,
+}
+
+impl<R: Read + Seek> Iterator for Ifds<'_, R> {
+    type Item = Result<Ifd, TiffError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset == 0 {
+            return None;
+        }
+        if self.visited.contains(&self.offset) {
+            let error: TiffError = TiffError::BadOffset {
+                tag: None,
+                offset: self.offset,
+                reason: "points back to an IFD earlier in the chain",
+            };
+            self.offset = 0;
+            // In lenient mode, the chain just ends here
+            return self.tiff_reader.violation(error).err().map(Err);
+        }
+        self.visited.push(self.offset);
+        match self.tiff_reader.read_ifd_at(self.offset) {
+            Ok(ifd) => {
+                self.offset = ifd.offset;
+                Some(Ok(ifd))
+            }
+            Err(error) => {
+                // There is no way to know where the next IFD is
+                self.offset = 0;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+// IFDs met while reading a tree of them, see process_ifd_tree(). A hostile file could otherwise
+// make the reader recurse forever, deep enough to overflow the stack, or over the same IFDs an
+// exponential number of times.
+#[derive(Default)]
+struct Visited {
+    // Offsets of the IFDs above the one being read
+    ancestors: Vec<u64>,
+    // Offsets of every IFD read so far, as no two pointers should lead to the same one
+    all: HashSet<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Endianness {
     Uninitialized,
     BigEndian,
     LittleEndian,
 }
 
+// What the first 8 bytes of the file say
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Header {
+    pub endianness: Endianness,
+    // Always 42, or the file is rejected
+    pub magic: u16,
+    pub first_ifd_offset: u64,
+}
+
 impl<R: Read + Seek> TiffReader<R> {
     /// # Errors
     ///
     /// Only those caused by the underlying reader
-    pub fn new(reader: R) -> Result<TiffReader<R>, Error> {
+    pub fn new(reader: R) -> Result<TiffReader<R>, TiffError> {
+        TiffReader::with_options(reader, ReaderOptions::default())
+    }
+
+    /// # Errors
+    ///
+    /// Only those caused by the underlying reader
+    pub fn with_options(reader: R, options: ReaderOptions) -> Result<TiffReader<R>, TiffError> {
         Ok(TiffReader {
             reader,
             endianness: Endianness::Uninitialized,
+            header: None,
+            options,
+            warnings: Vec::new(),
+            length: u64::MAX,
+            ifds_read: 0,
+            bytes_read: 0,
         })
     }
 
+    /// The file header, once it has been read, as any of the read methods do first
+    #[must_use]
+    pub fn header(&self) -> Option<Header> {
+        self.header
+    }
+
+    /// Spec violations found so far and let through because of lenient mode. `read_dng()` moves
+    /// them to the `Dng` it returns.
+    #[must_use]
+    pub fn warnings(&self) -> &[TiffError] {
+        &self.warnings
+    }
+
+    // Fails in strict mode, otherwise records the violation and lets the caller work around it
+    fn violation(&mut self, error: TiffError) -> Result<(), TiffError> {
+        if self.options.strict {
+            return Err(error);
+        }
+        self.warnings.push(error);
+        Ok(())
+    }
+
     /*********************************************************************************************
      *                        Methods that read high level data structures                       *
      *********************************************************************************************/
 
     /// # Panics
     ///
-    /// Only when underlying reader panics
+    /// Only when underlying reader panics. Malformed input, however broken its offsets, counts or
+    /// values, ends in an error: sizes and offsets read from the file are checked before they are
+    /// used for arithmetic, indexing or allocation.
     ///
     /// # Errors
     ///
     /// Those caused by the underlying reader, plus nonconformance to DNG 1.4.0.0
-    pub fn read_dng(&mut self) -> Result<Dng, Error> {
+    pub fn read_dng(&mut self) -> Result<Dng, TiffError> {
         let offset: u64 = self.process_header()?;
 
-        let ifd0 = self.process_ifd(offset)?;
+        let mut ifd0: Ifd = self.read_ifd_at(offset)?;
 
         // 𝐅𝐫𝐨𝐦 𝐃𝐢𝐠𝐢𝐭𝐚𝐥 𝐍𝐞𝐠𝐚𝐭𝐢𝐯𝐞 𝐒𝐩𝐞𝐜𝐢𝐟𝐢𝐜𝐚𝐭𝐢𝐨𝐧, 𝐕𝐞𝐫𝐬𝐢𝐨𝐧 𝟏.𝟒.𝟎.𝟎, 𝐩𝐚𝐠𝐞 𝟏𝟑
         //
         // 𝐷𝑁𝐺 𝑟𝑒𝑐𝑜𝑚𝑚𝑒𝑛𝑑𝑠 𝑡ℎ𝑒 𝑢𝑠𝑒 𝑜𝑓 𝑆𝑢𝑏𝐼𝐹𝐷 𝑡𝑟𝑒𝑒𝑠, 𝑎𝑠 𝑑𝑒𝑠𝑐𝑟𝑖𝑏𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 𝑇𝐼𝐹𝐹-𝐸𝑃 𝑠𝑝𝑒𝑐𝑖𝑓𝑖𝑐𝑎𝑡𝑖𝑜𝑛.
         // 𝑆𝑢𝑏𝐼𝐹𝐷 𝑐ℎ𝑎𝑖𝑛𝑠 𝑎𝑟𝑒 𝑛𝑜𝑡 𝑠𝑢𝑝𝑝𝑜𝑟𝑡𝑒𝑑.
         if ifd0.offset != 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                "DNG recommends the use of SubIFD trees, as described in the TIFF-EP specification. SubIFD chains are not supported.",
+            return Err(TiffError::InvalidData(
+                "DNG recommends the use of SubIFD trees, as described in the TIFF-EP specification. SubIFD chains are not supported.".to_string(),
             ));
         }
 
-        if let Some(field) = ifd0.fields.get(&Tag::SubIFDs) {
-            if let Field::Long(sub_ifds) = field {
-                if sub_ifds.len() != 1 {
-                    // 𝐅𝐫𝐨𝐦 𝐓𝐈𝐅𝐅/𝐄𝐏, 𝐩𝐚𝐠𝐞 𝟏𝟐
-                    //
-                    // 𝑇ℎ𝑒 𝑉𝑎𝑙𝑢𝑒 𝑤𝑖𝑙𝑙 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑡ℎ𝑒 𝑜𝑓𝑓𝑠𝑒𝑡 𝑡𝑜 𝑡ℎ𝑒 “𝑡𝑟𝑒𝑒𝑑” 𝐼𝐹𝐷 𝑖𝑡𝑠𝑒𝑙𝑓 𝑖𝑓 𝑁=1, 𝑜𝑡ℎ𝑒𝑟𝑤𝑖𝑠𝑒 𝑡ℎ𝑒
-                    // 𝑉𝑎𝑙𝑢𝑒 𝑤𝑖𝑙𝑙 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑎𝑛 𝑜𝑓𝑓𝑠𝑒𝑡 𝑡𝑜 𝑎 𝑙𝑜𝑐𝑎𝑡𝑖𝑜𝑛 𝑐𝑜𝑛𝑡𝑎𝑖𝑛𝑖𝑛𝑔 𝑎𝑛 𝑎𝑟𝑟𝑎𝑦 𝑜𝑓 𝑜𝑓𝑓𝑠𝑒𝑡𝑠 𝑡𝑜 𝑒𝑎𝑐ℎ
-                    // 𝐼𝐹𝐷 𝑏𝑒𝑖𝑛𝑔 “𝑡𝑟𝑒𝑒𝑑” 𝑓𝑟𝑜𝑚 𝑡ℎ𝑒 𝑐𝑢𝑟𝑟𝑒𝑛𝑡 𝐼𝐹𝐷. 𝑇ℎ𝑖𝑠 𝑎𝑟𝑟𝑎𝑦 𝑜𝑓 𝑜𝑓𝑓𝑠𝑒𝑡𝑠 𝑤𝑖𝑙𝑙 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑁
-                    // 𝑒𝑛𝑡𝑟𝑖𝑒𝑠, 𝑖.𝑒. 𝑜𝑓𝑓𝑠𝑒𝑡 𝑝𝑜𝑖𝑛𝑡𝑒𝑟𝑠 𝑡𝑜 𝑁 𝐼𝐹𝐷𝑠. 𝑪𝒖𝒓𝒓𝒆𝒏𝒕𝒍𝒚, 𝑵=1, 𝒂𝒏𝒅 𝒕𝒉𝒆 𝑽𝒂𝒍𝒖𝒆 𝒄𝒐𝒏𝒕𝒂𝒊𝒏𝒔
-                    // 𝒕𝒉𝒆 𝒐𝒇𝒇𝒔𝒆𝒕 𝒕𝒐 𝒕𝒉𝒆 𝑰𝑭𝑫 𝒄𝒐𝒏𝒕𝒂𝒊𝒏𝒊𝒏𝒈 𝒕𝒉𝒆 𝒇𝒖𝒍𝒍 𝒓𝒆𝒔𝒐𝒍𝒖𝒕𝒊𝒐𝒏 𝒊𝒎𝒂𝒈𝒆.
-                    //
-                    // 𝑈𝑠𝑎𝑔𝑒: 𝐼𝐹𝐷0
-                    return Err(Error::new(
-                        ErrorKind::InvalidData,
-                        "\"SubIFDs\" should only contain the offset to the IFD containing the full resolution image.",
-                    ));
-                }
+        if !ifd0.fields.contains_key(&Tag::SubIFDs) {
+            return Err(TiffError::InvalidData(
+                "TIFF/EP recommends that a thumbnail image be stored in the 0th IFD".to_string(),
+            ));
+        }
 
-                let offset: u64 = u64::from(sub_ifds[0]);
+        // Several cameras store thumbnail, preview and raw as separate SubIFDs, and DNG 1.5 and
+        // later add masks, depth maps and enhanced images. The raw one is the main image, and
+        // the other SubIFDs are found through Dng::sub_ifds_of_type().
+        let hires_index: Option<usize> = match ifd0.sub_ifds.as_slice() {
+            [_] => Some(0),
+            sub_ifds => sub_ifds.iter().position(|ifd| {
+                ifd.sub_file_type()
+                    .is_none_or(|sub_file_type| sub_file_type == SubFileType::Main)
+            }),
+        };
+        let Some(hires_index) = hires_index else {
+            // 𝐅𝐫𝐨𝐦 𝐓𝐈𝐅𝐅/𝐄𝐏, 𝐩𝐚𝐠𝐞 𝟏𝟐
+            //
+            // 𝑇ℎ𝑒 𝑉𝑎𝑙𝑢𝑒 𝑤𝑖𝑙𝑙 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑡ℎ𝑒 𝑜𝑓𝑓𝑠𝑒𝑡 𝑡𝑜 𝑡ℎ𝑒 “𝑡𝑟𝑒𝑒𝑑” 𝐼𝐹𝐷 𝑖𝑡𝑠𝑒𝑙𝑓 𝑖𝑓 𝑁=1, 𝑜𝑡ℎ𝑒𝑟𝑤𝑖𝑠𝑒 𝑡ℎ𝑒
+            // 𝑉𝑎𝑙𝑢𝑒 𝑤𝑖𝑙𝑙 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑎𝑛 𝑜𝑓𝑓𝑠𝑒𝑡 𝑡𝑜 𝑎 𝑙𝑜𝑐𝑎𝑡𝑖𝑜𝑛 𝑐𝑜𝑛𝑡𝑎𝑖𝑛𝑖𝑛𝑔 𝑎𝑛 𝑎𝑟𝑟𝑎𝑦 𝑜𝑓 𝑜𝑓𝑓𝑠𝑒𝑡𝑠 𝑡𝑜 𝑒𝑎𝑐ℎ
+            // 𝐼𝐹𝐷 𝑏𝑒𝑖𝑛𝑔 “𝑡𝑟𝑒𝑒𝑑” 𝑓𝑟𝑜𝑚 𝑡ℎ𝑒 𝑐𝑢𝑟𝑟𝑒𝑛𝑡 𝐼𝐹𝐷. 𝑇ℎ𝑖𝑠 𝑎𝑟𝑟𝑎𝑦 𝑜𝑓 𝑜𝑓𝑓𝑠𝑒𝑡𝑠 𝑤𝑖𝑙𝑙 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑁
+            // 𝑒𝑛𝑡𝑟𝑖𝑒𝑠, 𝑖.𝑒. 𝑜𝑓𝑓𝑠𝑒𝑡 𝑝𝑜𝑖𝑛𝑡𝑒𝑟𝑠 𝑡𝑜 𝑁 𝐼𝐹𝐷𝑠. 𝑪𝒖𝒓𝒓𝒆𝒏𝒕𝒍𝒚, 𝑵=1, 𝒂𝒏𝒅 𝒕𝒉𝒆 𝑽𝒂𝒍𝒖𝒆 𝒄𝒐𝒏𝒕𝒂𝒊𝒏𝒔
+            // 𝒕𝒉𝒆 𝒐𝒇𝒇𝒔𝒆𝒕 𝒕𝒐 𝒕𝒉𝒆 𝑰𝑭𝑫 𝒄𝒐𝒏𝒕𝒂𝒊𝒏𝒊𝒏𝒈 𝒕𝒉𝒆 𝒇𝒖𝒍𝒍 𝒓𝒆𝒔𝒐𝒍𝒖𝒕𝒊𝒐𝒏 𝒊𝒎𝒂𝒈𝒆.
+            //
+            // 𝑈𝑠𝑎𝑔𝑒: 𝐼𝐹𝐷0
+            return Err(TiffError::InvalidData(
+                "\"SubIFDs\" should contain the offset to the IFD containing the full resolution image.".to_string(),
+            ));
+        };
 
-                Ok(Dng {
-                    ifd0,
-                    hires_ifd: self.process_ifd(offset)?,
-                })
-            } else {
-                Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "\"SubIFDs\" should have type \"long\".",
-                ))
-            }
-        } else {
-            Err(Error::new(
-                ErrorKind::InvalidData,
-                "TIFF/EP recommends that a thumbnail image be stored in the 0th IFD",
-            ))
-        }
+        let hires_ifd: Ifd = ifd0.sub_ifds.remove(hires_index);
+
+        Ok(Dng {
+            ifd0,
+            hires_ifd,
+            hires_index,
+            warnings: std::mem::take(&mut self.warnings),
+        })
+    }
+
+    /// Same as `read_dng()`, but with every value read right away, however large, and the result
+    /// behind an `Arc`. With nothing left for `Field::load()` to do, the DNG never needs to change
+    /// again, so long running servers can cache it and hand it to any number of threads without
+    /// copying field buffers.
+    ///
+    /// # Errors
+    ///
+    /// Same as `read_dng()`
+    pub fn read_dng_shared(&mut self) -> Result<Arc<Dng>, TiffError> {
+        let inline_value_limit: usize =
+            std::mem::replace(&mut self.options.inline_value_limit, usize::MAX);
+        let result: Result<Dng, TiffError> = self.read_dng();
+        self.options.inline_value_limit = inline_value_limit;
+        result.map(Arc::new)
+    }
+
+    /// Iterates over the chain of IFDs starting at IFD0, reading each one only when asked for it,
+    /// so callers can stop early or walk long multi-page files without holding every IFD.
+    ///
+    /// # Errors
+    ///
+    /// Those caused by the underlying reader, plus an invalid TIFF header
+    pub fn ifds(&mut self) -> Result<Ifds<'_, R>, TiffError> {
+        let offset: u64 = self.process_header()?;
+        Ok(Ifds {
+            tiff_reader: self,
+            offset,
+            visited: Vec::new(),
+        })
+    }
+
+    /// Reads length bytes starting at offset, as they are in the file. Meant for data fields only
+    /// point to, such as image strips and tiles.
+    ///
+    /// # Errors
+    ///
+    /// Those caused by the underlying reader, plus fewer than length bytes available
+    pub fn read_bytes(&mut self, offset: u64, length: usize) -> Result<Vec<u8>, TiffError> {
+        self.check_range(offset, length)?;
+        self.count_bytes(length)?;
+        let mut buffer: Vec<u8> = new_buffer(length)?;
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.read_to(&mut buffer)?;
+        Ok(buffer)
     }
 
-    fn process_header(&mut self) -> Result<u64, Error> {
+    fn process_header(&mut self) -> Result<u64, TiffError> {
+        // Asking once beats finding out deep into parsing that an offset points nowhere. Except on
+        // a single pass, where finding the end means reading, and keeping, the whole stream. Bad
+        // offsets then only show up as truncated reads.
+        if !self.options.sequential {
+            self.length = self.reader.seek(SeekFrom::End(0))?;
+        }
+        self.reader.seek(SeekFrom::Start(0))?;
+
         // 𝐅𝐫𝐨𝐦 𝐓𝐈𝐅𝐅 𝟔.𝟎 𝐒𝐩𝐞𝐜𝐢𝐟𝐢𝐜𝐚𝐭𝐢𝐨𝐧, 𝐩𝐚𝐠𝐞 𝟏𝟑
         //
         // 𝐼𝑚𝑎𝑔𝑒 𝐹𝑖𝑙𝑒 𝐻𝑒𝑎𝑑𝑒𝑟
@@ -173,8 +939,7 @@ impl<R: Read + Seek> TiffReader<R> {
         } else if buffer[0] == 0x4D && buffer[1] == 0x4D {
             self.endianness = Endianness::BigEndian;
         } else {
-            return Err(Error::new(
-            ErrorKind::InvalidData,
+            return Err(TiffError::InvalidHeader(
                 format!(
                     "Invalid byte order specification: {:?}. Legal values are “II” (4949.H) and “MM” (4D4D.H)",
                     &buffer
@@ -188,8 +953,7 @@ impl<R: Read + Seek> TiffReader<R> {
         //            𝑇ℎ𝑒 𝑏𝑦𝑡𝑒 𝑜𝑟𝑑𝑒𝑟 𝑑𝑒𝑝𝑒𝑛𝑑𝑠 𝑜𝑛 𝑡ℎ𝑒 𝑣𝑎𝑙𝑢𝑒 𝑜𝑓 𝐵𝑦𝑡𝑒𝑠 0-1.
         let version: u16 = self.read_u16()?;
         if version != 42 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
+            return Err(TiffError::InvalidHeader(
                 format!("Failed to further identify the file as a TIFF file, was expecting 42, found {version}"),
             ));
         }
@@ -202,7 +966,7 @@ impl<R: Read + Seek> TiffReader<R> {
         //            𝑇ℎ𝑒 𝑡𝑒𝑟𝑚 𝑏𝑦𝑡𝑒 𝑜𝑓𝑓𝑠𝑒𝑡 𝑖𝑠 𝑎𝑙𝑤𝑎𝑦𝑠 𝑢𝑠𝑒𝑑 𝑖𝑛 𝑡ℎ𝑖𝑠 𝑑𝑜𝑐𝑢𝑚𝑒𝑛𝑡 𝑡𝑜 𝑟𝑒𝑓𝑒𝑟 𝑡𝑜 𝑎 𝑙𝑜𝑐𝑎𝑡𝑖𝑜𝑛
         //            𝑤𝑖𝑡ℎ 𝑟𝑒𝑠𝑝𝑒𝑐𝑡 𝑡𝑜 𝑡ℎ𝑒 𝑏𝑒𝑔𝑖𝑛𝑛𝑖𝑛𝑔 𝑜𝑓 𝑡ℎ𝑒 𝑇𝐼𝐹𝐹 𝑓𝑖𝑙𝑒. 𝑇ℎ𝑒 𝑓𝑖𝑟𝑠𝑡 𝑏𝑦𝑡𝑒 𝑜𝑓 𝑡ℎ𝑒 𝑓𝑖𝑙𝑒
         //            ℎ𝑎𝑠 𝑎𝑛 𝑜𝑓𝑓𝑠𝑒𝑡 𝑜𝑓 0.
-        let offset: u64 = self.read_offset()?;
+        let offset: u64 = self.read_offset(self.options.odd_ifd_offsets)?;
 
         // 𝐅𝐫𝐨𝐦 𝐓𝐈𝐅𝐅 𝟔.𝟎 𝐒𝐩𝐞𝐜𝐢𝐟𝐢𝐜𝐚𝐭𝐢𝐨𝐧, 𝐩𝐚𝐠𝐞 𝟏𝟒
         //
@@ -211,19 +975,86 @@ impl<R: Read + Seek> TiffReader<R> {
         // As a side effect, we also fail here if offset == 0, that is, there are no IFDs in the
         // file.
         if offset < 8 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("First IFD offset is smaller than header size: {offset}"),
-            ));
+            return Err(TiffError::InvalidHeader(format!(
+                "First IFD offset is smaller than header size: {offset}"
+            )));
         }
 
+        self.header = Some(Header {
+            endianness: self.endianness,
+            magic: version,
+            first_ifd_offset: offset,
+        });
         Ok(offset)
     }
 
-    fn process_ifd(&mut self, offset: u64) -> Result<Ifd, Error> {
+    /// Reads the IFD at offset, along with the IFDs its `SubIFDs`, `ExifIFD` and `GPSInfo` fields
+    /// point to, for callers following pointers they found themselves, such as maker note
+    /// directories. The file header is read first, if it has not been yet, to learn the byte order.
+    ///
+    /// # Errors
+    ///
+    /// Those caused by the underlying reader, plus an invalid TIFF header or IFD
+    pub fn read_ifd_at(&mut self, offset: u64) -> Result<Ifd, TiffError> {
+        if self.header.is_none() {
+            self.process_header()?;
+        }
+        self.process_ifd_tree(offset, Tag::new, &mut Visited::default())
+    }
+
+    /// Same as `read_ifd_at()`, but reading the IFD in the given byte order instead of that of
+    /// the file, as some maker notes embedded in files of the other byte order need. Every value
+    /// is read right away, since `Field::load()` would later use the byte order of the file.
+    ///
+    /// # Errors
+    ///
+    /// Those caused by the underlying reader, plus an invalid TIFF header or IFD
+    pub fn read_ifd_at_with_byte_order(
+        &mut self,
+        offset: u64,
+        endianness: Endianness,
+    ) -> Result<Ifd, TiffError> {
+        if self.header.is_none() {
+            self.process_header()?;
+        }
+        let file_endianness: Endianness = std::mem::replace(&mut self.endianness, endianness);
+        let inline_value_limit: usize =
+            std::mem::replace(&mut self.options.inline_value_limit, usize::MAX);
+        let result: Result<Ifd, TiffError> =
+            self.process_ifd_tree(offset, Tag::new, &mut Visited::default());
+        self.endianness = file_endianness;
+        self.options.inline_value_limit = inline_value_limit;
+        result
+    }
+
+    // Reads the IFD at offset and, recursively, the IFDs its SubIFDs, ExifIFD and GPSInfo fields
+    // point to. New_tag maps tag numbers to tags, which depends on the kind of IFD. Visited holds
+    // the offsets of the IFDs read so far, to detect pointers back at them.
+    fn process_ifd_tree(
+        &mut self,
+        offset: u64,
+        new_tag: fn(u16) -> Tag,
+        visited: &mut Visited,
+    ) -> Result<Ifd, TiffError> {
+        self.ifds_read += 1;
+        if self.ifds_read > self.options.max_ifds {
+            return Err(TiffError::LimitExceeded {
+                limit: "IFD count",
+                maximum: self.options.max_ifds,
+            });
+        }
+        if visited.ancestors.len() >= self.options.max_ifd_depth {
+            return Err(TiffError::LimitExceeded {
+                limit: "IFD depth",
+                maximum: self.options.max_ifd_depth,
+            });
+        }
+        visited.all.insert(offset);
+        self.check_range(offset, 2)?;
         self.reader.seek(SeekFrom::Start(offset))?;
 
-        let mut fields: HashMap<Tag, Field> = HashMap::<Tag, Field>::new();
+        let mut fields: Fields = Fields::new();
+        let mut duplicates: Vec<(Tag, Field)> = Vec::new();
 
         // Note: TIFF 6.0 Specification uses the terms "IFD Entry" and "field" with the same
         // meaning, this is sometimes confusing.
@@ -239,19 +1070,27 @@ impl<R: Read + Seek> TiffReader<R> {
         //
         // 𝑇ℎ𝑒𝑟𝑒 𝑚𝑢𝑠𝑡 𝑏𝑒 𝑎𝑡 𝑙𝑒𝑎𝑠𝑡 1 𝐼𝐹𝐷 𝑖𝑛 𝑎 𝑇𝐼𝐹𝐹 𝑓𝑖𝑙𝑒 𝑎𝑛𝑑 𝑒𝑎𝑐ℎ 𝐼𝐹𝐷 𝑚𝑢𝑠𝑡 ℎ𝑎𝑣𝑒 𝑎𝑡 𝑙𝑒𝑎𝑠𝑡 𝑜𝑛𝑒 𝑒𝑛𝑡𝑟𝑦.
         let number_of_fields: u16 = self.read_u16()?;
+        if usize::from(number_of_fields) > self.options.max_fields_per_ifd {
+            return Err(TiffError::LimitExceeded {
+                limit: "fields per IFD",
+                maximum: self.options.max_fields_per_ifd,
+            });
+        }
+        // Entries and next IFD offset
+        self.check_range(offset, 2 + 12 * usize::from(number_of_fields) + 4)?;
+        if number_of_fields < 1 {
+            // Its next IFD offset is still there, so the chain can go on
+            self.violation(TiffError::InvalidData(format!(
+                "IFD at offset {offset} has no entries"
+            )))?;
+        }
         for _i in 0..number_of_fields {
             // 𝐼𝐹𝐷 𝐸𝑛𝑡𝑟𝑦
             //
             // 𝐸𝑎𝑐ℎ 12-𝑏𝑦𝑡𝑒 𝐼𝐹𝐷 𝑒𝑛𝑡𝑟𝑦 ℎ𝑎𝑠 𝑡ℎ𝑒 𝑓𝑜𝑙𝑙𝑜𝑤𝑖𝑛𝑔 𝑓𝑜𝑟𝑚𝑎𝑡:
             //
             // 𝐵𝑦𝑡𝑒𝑠 0-1 𝑇ℎ𝑒 𝑇𝑎𝑔 𝑡ℎ𝑎𝑡 𝑖𝑑𝑒𝑛𝑡𝑖𝑓𝑖𝑒𝑠 𝑡ℎ𝑒 𝑓𝑖𝑒𝑙𝑑.
-            let tag: Tag = self.read_tag()?;
-
-            // TODO we do not need to know or process all tags, remove the ones we don't care about
-            // uncomment this after testing is done.
-            if tag == Tag::Unknown {
-                break;
-            }
+            let tag: Tag = new_tag(self.read_u16()?);
 
             // 𝐵𝑦𝑡𝑒𝑠 2-3 𝑇ℎ𝑒 𝑓𝑖𝑒𝑙𝑑 𝑇𝑦𝑝𝑒.
             let type_: u16 = self.read_u16()?;
@@ -260,48 +1099,214 @@ impl<R: Read + Seek> TiffReader<R> {
             //
             // 𝑊𝑎𝑟𝑛𝑖𝑛𝑔: 𝐼𝑡 𝑖𝑠 𝑝𝑜𝑠𝑠𝑖𝑏𝑙𝑒 𝑡ℎ𝑎𝑡 𝑜𝑡ℎ𝑒𝑟 𝑇𝐼𝐹𝐹 𝑓𝑖𝑒𝑙𝑑 𝑡𝑦𝑝𝑒𝑠 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑎𝑑𝑑𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 𝑓𝑢𝑡𝑢𝑟𝑒. 𝑅𝑒𝑎𝑑𝑒𝑟𝑠 𝑠ℎ𝑜𝑢𝑙𝑑
             //          𝑠𝑘𝑖𝑝 𝑜𝑣𝑒𝑟 𝑓𝑖𝑒𝑙𝑑𝑠 𝑐𝑜𝑛𝑡𝑎𝑖𝑛𝑖𝑛𝑔 𝑎𝑛 𝑢𝑛𝑒𝑥𝑝𝑒𝑐𝑡𝑒𝑑 𝑓𝑖𝑒𝑙𝑑 𝑡𝑦𝑝𝑒.
-            if type_ < 1 {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "A field type cannot be zero",
-                ));
-            }
-            if type_ > 12 {
-                break;
+            if !matches!(type_, BYTE..=IFD | LONG8..=IFD8 | UTF8) {
+                self.violation(TiffError::UnexpectedType { tag, type_ })?;
+                // Skips Count and Value Offset
+                self.reader.seek(SeekFrom::Current(8))?;
+                continue;
             }
 
             // 𝐵𝑦𝑡𝑒𝑠 4-7 𝑇ℎ𝑒 𝑛𝑢𝑚𝑏𝑒𝑟 𝑜𝑓 𝑣𝑎𝑙𝑢𝑒𝑠, 𝐶𝑜𝑢𝑛𝑡 𝑜𝑓 𝑡ℎ𝑒 𝑖𝑛𝑑𝑖𝑐𝑎𝑡𝑒𝑑 𝑇𝑦𝑝𝑒.
             let count: u32 = self.read_u32()?;
 
             if count < 1 {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    format!("Field should have at least one value: {count}"),
-                ));
+                self.violation(TiffError::InvalidData(format!(
+                    "\"{tag:?}\" should have at least one value"
+                )))?;
+                // Skips Value Offset
+                self.reader.seek(SeekFrom::Current(4))?;
+                continue;
             }
 
-            fields.insert(tag, self.read_ifd_field(type_, count)?);
+            let field: Field = self
+                .read_ifd_field(type_, count)
+                .map_err(|error| error.with_tag(tag))?;
+            self.add_field(&mut fields, &mut duplicates, tag, field)?;
         }
 
+        let next_offset: u64 = self.read_offset(self.options.odd_ifd_offsets)?;
+
+        if self.options.sequential {
+            self.load_fields_in_order(
+                fields
+                    .iter_mut()
+                    .chain(duplicates.iter_mut().map(|(tag, field)| (&*tag, field))),
+            )?;
+        }
+
+        visited.ancestors.push(offset);
+        let sub_ifds: Vec<Ifd> =
+            self.process_child_ifds(&mut fields, Tag::SubIFDs, Tag::new, visited)?;
+        let exif: Option<Box<Ifd>> = self
+            .process_child_ifds(&mut fields, Tag::ExifIFD, Tag::new, visited)?
+            .into_iter()
+            .next()
+            .map(Box::new);
+        let gps: Option<Box<Ifd>> = self
+            .process_child_ifds(&mut fields, Tag::GPSInfo, Tag::new_gps, visited)?
+            .into_iter()
+            .next()
+            .map(Box::new);
+        let interoperability: Option<Box<Ifd>> = self
+            .process_child_ifds(
+                &mut fields,
+                Tag::InteroperabilityIFD,
+                Tag::new_interoperability,
+                visited,
+            )?
+            .into_iter()
+            .next()
+            .map(Box::new);
+        visited.ancestors.pop();
+
         Ok(Ifd {
             fields,
-            offset: self.read_offset()?,
+            offset: next_offset,
+            position: offset,
+            sub_ifds,
+            exif,
+            gps,
+            interoperability,
+            duplicates,
         })
     }
 
-    fn read_ifd_field(&mut self, type_: u16, count: u32) -> Result<Field, Error> {
-        let size: usize =
-            type_size(type_)? * <u32 as std::convert::TryInto<usize>>::try_into(count).unwrap();
-        let mut buffer: Vec<u8> = Vec::with_capacity(size);
-        buffer.spare_capacity_mut();
-        unsafe {
-            // See https://rust-lang.github.io/rust-clippy/master/index.html#uninit_vec
-            // See https://doc.rust-lang.org/std/vec/struct.Vec.html#method.spare_capacity_mut
-            //
-            // This is a Rust hack, but it is OK, because we do not read data until after we have
-            // written it, which we do below, before returning from this function.
-            buffer.set_len(size);
+    // 𝐅𝐫𝐨𝐦 𝐓𝐈𝐅𝐅 𝟔.𝟎 𝐒𝐩𝐞𝐜𝐢𝐟𝐢𝐜𝐚𝐭𝐢𝐨𝐧, 𝐩𝐚𝐠𝐞 𝟏𝟓
+    //
+    // 𝑇ℎ𝑒 𝑒𝑛𝑡𝑟𝑖𝑒𝑠 𝑖𝑛 𝑎𝑛 𝐼𝐹𝐷 𝑚𝑢𝑠𝑡 𝑏𝑒 𝑠𝑜𝑟𝑡𝑒𝑑 𝑖𝑛 𝑎𝑠𝑐𝑒𝑛𝑑𝑖𝑛𝑔 𝑜𝑟𝑑𝑒𝑟 𝑏𝑦 𝑇𝑎𝑔.
+    //
+    // Which leaves no room for the same tag twice, yet malformed files do that. What happens to
+    // fields already there is up to the options.
+    fn add_field(
+        &mut self,
+        fields: &mut Fields,
+        duplicates: &mut Vec<(Tag, Field)>,
+        tag: Tag,
+        field: Field,
+    ) -> Result<(), TiffError> {
+        match fields.get_mut(&tag) {
+            None => {
+                fields.insert(tag, field);
+            }
+            Some(existing) => {
+                if self.options.duplicate_tags == DuplicateTags::Error {
+                    return Err(TiffError::DuplicateTag(tag));
+                }
+                if !self.options.strict {
+                    self.warnings.push(TiffError::DuplicateTag(tag));
+                }
+                match self.options.duplicate_tags {
+                    DuplicateTags::Error | DuplicateTags::KeepFirst => {}
+                    DuplicateTags::KeepLast => *existing = field,
+                    DuplicateTags::KeepAll => duplicates.push((tag, field)),
+                }
+            }
         }
+        Ok(())
+    }
+
+    fn load_fields_in_order<'a>(
+        &mut self,
+        fields: impl Iterator<Item = (&'a Tag, &'a mut Field)>,
+    ) -> Result<(), TiffError> {
+        // Values above the limit are meant to stay lazy
+        let limit: usize = self.options.inline_value_limit;
+        let mut lazy_fields: Vec<(&Tag, &mut Field)> = fields
+            .filter(|(_, field)| match field {
+                Field::Lazy { type_, count, .. } => {
+                    value_size(*type_, *count).is_ok_and(|size| size <= limit)
+                }
+                _ => false,
+            })
+            .collect();
+        lazy_fields.sort_by_key(|(_, field)| match field {
+            Field::Lazy { offset, .. } => *offset,
+            _ => 0,
+        });
+
+        for (tag, field) in lazy_fields {
+            if let Field::Lazy {
+                type_,
+                count,
+                offset,
+            } = *field
+            {
+                *field = self
+                    .load_field(type_, count, offset)
+                    .map_err(|error| error.with_tag(*tag))?;
+            }
+        }
+        Ok(())
+    }
+
+    // 𝐅𝐫𝐨𝐦 𝐓𝐈𝐅𝐅/𝐄𝐏, 𝐩𝐚𝐠𝐞 𝟏𝟐
+    //
+    // 𝑇ℎ𝑒 𝑉𝑎𝑙𝑢𝑒 𝑤𝑖𝑙𝑙 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑡ℎ𝑒 𝑜𝑓𝑓𝑠𝑒𝑡 𝑡𝑜 𝑡ℎ𝑒 “𝑡𝑟𝑒𝑒𝑑” 𝐼𝐹𝐷 𝑖𝑡𝑠𝑒𝑙𝑓 𝑖𝑓 𝑁=1, 𝑜𝑡ℎ𝑒𝑟𝑤𝑖𝑠𝑒 𝑡ℎ𝑒 𝑉𝑎𝑙𝑢𝑒 𝑤𝑖𝑙𝑙
+    // 𝑐𝑜𝑛𝑡𝑎𝑖𝑛 𝑎𝑛 𝑜𝑓𝑓𝑠𝑒𝑡 𝑡𝑜 𝑎 𝑙𝑜𝑐𝑎𝑡𝑖𝑜𝑛 𝑐𝑜𝑛𝑡𝑎𝑖𝑛𝑖𝑛𝑔 𝑎𝑛 𝑎𝑟𝑟𝑎𝑦 𝑜𝑓 𝑜𝑓𝑓𝑠𝑒𝑡𝑠 𝑡𝑜 𝑒𝑎𝑐ℎ 𝐼𝐹𝐷 𝑏𝑒𝑖𝑛𝑔 “𝑡𝑟𝑒𝑒𝑑” 𝑓𝑟𝑜𝑚
+    // 𝑡ℎ𝑒 𝑐𝑢𝑟𝑟𝑒𝑛𝑡 𝐼𝐹𝐷.
+    //
+    // The same goes for other fields pointing to IFDs, such as ExifIFD, except that they always
+    // point to a single one.
+    fn process_child_ifds(
+        &mut self,
+        fields: &mut Fields,
+        tag: Tag,
+        new_tag: fn(u16) -> Tag,
+        visited: &mut Visited,
+    ) -> Result<Vec<Ifd>, TiffError> {
+        let mut children: Vec<Ifd> = Vec::new();
+
+        if let Some(field) = fields.get_mut(&tag) {
+            // Offsets are needed right away, even in lazy mode
+            if let Field::Lazy {
+                type_,
+                count,
+                offset,
+            } = *field
+            {
+                *field = self.load_field(type_, count, offset)?;
+            }
+
+            let offsets: Option<Vec<u64>> = match field {
+                Field::Long(offsets) | Field::Ifd(offsets) => {
+                    Some(offsets.iter().map(|offset| u64::from(*offset)).collect())
+                }
+                Field::Long8(offsets) | Field::Ifd8(offsets) => Some(offsets.clone()),
+                _ => None,
+            };
+            if let Some(offsets) = offsets {
+                for offset in offsets {
+                    if visited.ancestors.contains(&offset) {
+                        self.violation(TiffError::BadOffset {
+                            tag: Some(tag),
+                            offset,
+                            reason: "points to an IFD that is its own ancestor",
+                        })?;
+                        continue;
+                    }
+                    if visited.all.contains(&offset) {
+                        self.violation(TiffError::BadOffset {
+                            tag: Some(tag),
+                            offset,
+                            reason: "points to an IFD already read",
+                        })?;
+                        continue;
+                    }
+                    children.push(self.process_ifd_tree(offset, new_tag, visited)?);
+                }
+            } else {
+                self.violation(TiffError::UnexpectedType {
+                    tag,
+                    type_: field_type(field),
+                })?;
+            }
+        }
+
+        Ok(children)
+    }
+
+    fn read_ifd_field(&mut self, type_: u16, count: u32) -> Result<Field, TiffError> {
+        let size: usize = value_size(type_, count)?;
 
         // 𝐵𝑦𝑡𝑒𝑠 8-11 𝑇ℎ𝑒 𝑉𝑎𝑙𝑢𝑒 𝑂𝑓𝑓𝑠𝑒𝑡, 𝑡ℎ𝑒 𝑓𝑖𝑙𝑒 𝑜𝑓𝑓𝑠𝑒𝑡 (𝑖𝑛 𝑏𝑦𝑡𝑒𝑠) 𝑜𝑓 𝑡ℎ𝑒 𝑉𝑎𝑙𝑢𝑒 𝑓𝑜𝑟 𝑡ℎ𝑒 𝑓𝑖𝑒𝑙𝑑.
         //
@@ -315,42 +1320,73 @@ impl<R: Read + Seek> TiffReader<R> {
         // 𝑙𝑜𝑤𝑒𝑟-𝑛𝑢𝑚𝑏𝑒𝑟𝑒𝑑 𝑏𝑦𝑡𝑒𝑠. 𝑊ℎ𝑒𝑡ℎ𝑒𝑟 𝑡ℎ𝑒 𝑉𝑎𝑙𝑢𝑒 𝑓𝑖𝑡𝑠 𝑤𝑖𝑡ℎ𝑖𝑛 4 𝑏𝑦𝑡𝑒𝑠 𝑖𝑠 𝑑𝑒𝑡𝑒𝑟𝑚𝑖𝑛𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒
         // 𝑇𝑦𝑝𝑒 𝑎𝑛𝑑 𝐶𝑜𝑢𝑛𝑡 𝑜𝑓 𝑡ℎ𝑒 𝑓𝑖𝑒𝑙𝑑.
         if size > 4 {
-            let offset: u64 = self.read_offset()?;
+            let offset: u64 = self.read_offset(self.options.odd_value_offsets)?;
+            self.check_range(offset, size)?;
+            if size > self.options.inline_value_limit || self.options.sequential {
+                return Ok(Field::Lazy {
+                    type_,
+                    count,
+                    offset,
+                });
+            }
             let current_offset: u64 = self.reader.stream_position()?;
-            self.reader.seek(SeekFrom::Start(offset))?;
-            self.read_to(&mut buffer)?;
+            let field: Field = self.load_field(type_, count, offset)?;
             self.reader.seek(SeekFrom::Start(current_offset))?;
+            Ok(field)
         } else {
-            self.read_to(&mut buffer)?;
-            self.reader
-                .seek(SeekFrom::Current((4 - size).try_into().unwrap()))?;
+            // Reading all 4 bytes leaves the reader at the next entry, no seeking needed
+            let value: [u8; 4] = self.read_to_stack()?;
+            self.decode_field(type_, value[..size].to_vec())
         }
+    }
 
-        Ok(match type_ {
-            ASCII => to_ascii_field(&buffer),
-            DOUBLE => self.to_double_field(&buffer)?,
-            FLOAT => self.to_float_field(&buffer)?,
-            LONG => self.to_long_field(&buffer)?,
-            RATIONAL => self.to_rational_field(&buffer)?,
-            SBYTE => self.to_sbyte_field(&buffer)?,
-            SHORT => self.to_short_field(&buffer)?,
-            SLONG => self.to_slong_field(&buffer)?,
-            SRATIONAL => self.to_srational_field(&buffer)?,
-            SSHORT => self.to_sshort_field(&buffer)?,
-            // BYTE, UNDEFINED and unknown types just return an array of bytes
-            _ => Field::Byte(buffer),
-        })
+    fn load_field(&mut self, type_: u16, count: u32, offset: u64) -> Result<Field, TiffError> {
+        let size: usize = value_size(type_, count)?;
+        if size > self.options.max_field_bytes {
+            return Err(TiffError::LimitExceeded {
+                limit: "field size",
+                maximum: self.options.max_field_bytes,
+            });
+        }
+        self.count_bytes(size)?;
+        let mut buffer: Vec<u8> = new_buffer(size)?;
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.read_to(&mut buffer)?;
+        self.decode_field(type_, buffer)
     }
 
     /*********************************************************************************************
      *                             Methods that read data from file                              *
      *********************************************************************************************/
 
-    fn read_tag(&mut self) -> Result<Tag, Error> {
-        Ok(Tag::new(self.read_u16()?))
+    // Fails if reading size more bytes into memory would go over the limit
+    fn count_bytes(&mut self, size: usize) -> Result<(), TiffError> {
+        self.bytes_read = self.bytes_read.saturating_add(size);
+        if self.bytes_read > self.options.max_total_bytes {
+            return Err(TiffError::LimitExceeded {
+                limit: "total size",
+                maximum: self.options.max_total_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    // Fails unless size bytes starting at offset are all within the file
+    fn check_range(&self, offset: u64, size: usize) -> Result<(), TiffError> {
+        let end: Option<u64> = u64::try_from(size)
+            .ok()
+            .and_then(|size| offset.checked_add(size));
+        if end.is_none_or(|end| end > self.length) {
+            return Err(TiffError::BadOffset {
+                tag: None,
+                offset,
+                reason: "points past the end of the file",
+            });
+        }
+        Ok(())
     }
 
-    fn read_offset(&mut self) -> Result<u64, Error> {
+    fn read_offset(&mut self, odd_offsets: OddOffsets) -> Result<u64, TiffError> {
         // TIFF uses 32 bit for offsets, but std::io::Seek expects 64 bits
         let offset: u64 = u64::from(self.read_u32()?);
 
@@ -359,39 +1395,35 @@ impl<R: Read + Seek> TiffReader<R> {
         // 𝑇ℎ𝑒 𝑑𝑖𝑟𝑒𝑐𝑡𝑜𝑟𝑦 𝑚𝑎𝑦 𝑏𝑒 𝑎𝑡 𝑎𝑛𝑦 𝑙𝑜𝑐𝑎𝑡𝑖𝑜𝑛 𝑖𝑛 𝑡ℎ𝑒 𝑓𝑖𝑙𝑒 𝑎𝑓𝑡𝑒𝑟 𝑡ℎ𝑒 ℎ𝑒𝑎𝑑𝑒𝑟 𝑏𝑢𝑡 𝑚𝑢𝑠𝑡 𝑏𝑒𝑔𝑖𝑛 𝑜𝑛 𝑎 𝑤𝑜𝑟𝑑
         // 𝑏𝑜𝑢𝑛𝑑𝑎𝑟𝑦.
         if offset % 2 == 1 {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("Value offset is odd and therefore not a word boundary: {offset}"),
-            ));
+            let error: TiffError = TiffError::BadOffset {
+                tag: None,
+                offset,
+                reason: "is odd and therefore not a word boundary",
+            };
+            match odd_offsets {
+                OddOffsets::Violation => self.violation(error)?,
+                OddOffsets::Error => return Err(error),
+                OddOffsets::Allow => {}
+            }
         }
         Ok(offset)
     }
 
-    fn read_u16(&mut self) -> Result<u16, Error> {
+    fn read_u16(&mut self) -> Result<u16, TiffError> {
         let buffer: [u8; 2] = self.read_to_stack()?;
         Ok(match self.endianness {
             Endianness::LittleEndian => u16::from_le_bytes(buffer),
             Endianness::BigEndian => u16::from_be_bytes(buffer),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn read_u32(&mut self) -> Result<u32, Error> {
+    fn read_u32(&mut self) -> Result<u32, TiffError> {
         let buffer: [u8; 4] = self.read_to_stack()?;
         Ok(match self.endianness {
             Endianness::LittleEndian => u32::from_le_bytes(buffer),
             Endianness::BigEndian => u32::from_be_bytes(buffer),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
@@ -399,22 +1431,30 @@ impl<R: Read + Seek> TiffReader<R> {
     // realized I would also need one to read vectors.
     //
     // I may remove this function in case it looks like it became redundant and offers no benefit.
-    fn read_to_stack<const SIZE: usize>(&mut self) -> Result<[u8; SIZE], Error> {
+    fn read_to_stack<const SIZE: usize>(&mut self) -> Result<[u8; SIZE], TiffError> {
         let mut buffer: [u8; SIZE] = [0u8; SIZE];
         self.read_to(&mut buffer)?;
         Ok(buffer)
     }
 
-    fn read_to(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
-        let bytes_read: usize = self.reader.read(buffer)?;
+    // Read::read() may return fewer bytes than asked for even when more are coming, as sockets and
+    // chained readers do, so it is called until the buffer is full or there is nothing left.
+    fn read_to(&mut self, buffer: &mut [u8]) -> Result<(), TiffError> {
+        let mut bytes_read: usize = 0;
+        while bytes_read < buffer.len() {
+            match self.reader.read(&mut buffer[bytes_read..]) {
+                Ok(0) => break,
+                Ok(count) => bytes_read += count,
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(TiffError::Io(error)),
+            }
+        }
         if bytes_read != buffer.len() {
-            return Err(Error::new(
-                ErrorKind::UnexpectedEof,
-                format!(
-                    "Tried to read {} bytes, found only {bytes_read} bytes available",
-                    buffer.len()
-                ),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: buffer.len(),
+                found: bytes_read,
+            });
         }
         Ok(())
     }
@@ -423,7 +1463,41 @@ impl<R: Read + Seek> TiffReader<R> {
      *                         Methods that convert byte arrays to Fields                        *
      *********************************************************************************************/
 
-    fn to_double_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn decode_field(&mut self, type_: u16, buffer: Vec<u8>) -> Result<Field, TiffError> {
+        Ok(match type_ {
+            ASCII => {
+                // The count includes the NUL ending the last string, so the last byte should
+                // always be one. So many cameras leave it out of Make, Model or Software that
+                // the value is taken as it is, even in strict mode, with only a warning in
+                // lenient mode.
+                if !self.options.strict && buffer.last().is_some_and(|byte| *byte != 0) {
+                    self.warnings.push(TiffError::InvalidData(format!(
+                        "ASCII value {:?} is not NUL terminated",
+                        decode_ascii(&buffer)
+                    )));
+                }
+                to_ascii_field(&buffer)
+            }
+            UTF8 => to_utf8_field(&buffer),
+            DOUBLE => self.to_double_field(&buffer)?,
+            FLOAT => self.to_float_field(&buffer)?,
+            LONG => self.to_long_field(&buffer)?,
+            RATIONAL => self.to_rational_field(&buffer)?,
+            SBYTE => self.to_sbyte_field(&buffer)?,
+            SHORT => self.to_short_field(&buffer)?,
+            SLONG => self.to_slong_field(&buffer)?,
+            SRATIONAL => self.to_srational_field(&buffer)?,
+            SSHORT => self.to_sshort_field(&buffer)?,
+            IFD => self.to_ifd_field(&buffer)?,
+            LONG8 => self.to_long8_field(&buffer)?,
+            SLONG8 => self.to_slong8_field(&buffer)?,
+            IFD8 => self.to_ifd8_field(&buffer)?,
+            // BYTE, UNDEFINED and unknown types just return an array of bytes
+            _ => Field::Byte(buffer),
+        })
+    }
+
+    fn to_double_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(DOUBLE)?;
 
         let mut data: Vec<Double> = Vec::<Double>::new();
@@ -433,7 +1507,7 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Double(data))
     }
 
-    fn to_float_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_float_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(FLOAT)?;
 
         let mut data: Vec<Float> = Vec::<Float>::new();
@@ -443,7 +1517,7 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Float(data))
     }
 
-    fn to_long_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_long_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(LONG)?;
 
         let mut data: Vec<Long> = Vec::<Long>::new();
@@ -453,7 +1527,47 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Long(data))
     }
 
-    fn to_rational_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_ifd_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
+        let size: usize = type_size(IFD)?;
+
+        let mut data: Vec<Long> = Vec::<Long>::new();
+        for i in 0..buffer.len() / size {
+            data.push(self.to_long(&buffer[size * i..size * i + size])?);
+        }
+        Ok(Field::Ifd(data))
+    }
+
+    fn to_long8_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
+        let size: usize = type_size(LONG8)?;
+
+        let mut data: Vec<Long8> = Vec::<Long8>::new();
+        for i in 0..buffer.len() / size {
+            data.push(self.to_long8(&buffer[size * i..size * i + size])?);
+        }
+        Ok(Field::Long8(data))
+    }
+
+    fn to_slong8_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
+        let size: usize = type_size(SLONG8)?;
+
+        let mut data: Vec<Slong8> = Vec::<Slong8>::new();
+        for i in 0..buffer.len() / size {
+            data.push(self.to_slong8(&buffer[size * i..size * i + size])?);
+        }
+        Ok(Field::Slong8(data))
+    }
+
+    fn to_ifd8_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
+        let size: usize = type_size(IFD8)?;
+
+        let mut data: Vec<Long8> = Vec::<Long8>::new();
+        for i in 0..buffer.len() / size {
+            data.push(self.to_long8(&buffer[size * i..size * i + size])?);
+        }
+        Ok(Field::Ifd8(data))
+    }
+
+    fn to_rational_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(RATIONAL)?;
 
         let mut data: Vec<num_rational::Ratio<Long>> = Vec::<num_rational::Ratio<Long>>::new();
@@ -463,7 +1577,7 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Rational(data))
     }
 
-    fn to_sbyte_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_sbyte_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(SBYTE)?;
 
         let mut data: Vec<Sbyte> = Vec::<Sbyte>::new();
@@ -473,7 +1587,7 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Sbyte(data))
     }
 
-    fn to_short_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_short_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(SHORT)?;
 
         let mut data: Vec<Short> = Vec::<Short>::new();
@@ -483,7 +1597,7 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Short(data))
     }
 
-    fn to_slong_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_slong_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(SLONG)?;
 
         let mut data: Vec<Slong> = Vec::<Slong>::new();
@@ -493,7 +1607,7 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Slong(data))
     }
 
-    fn to_srational_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_srational_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(SRATIONAL)?;
 
         let mut data: Vec<num_rational::Ratio<Slong>> = Vec::<num_rational::Ratio<Slong>>::new();
@@ -503,7 +1617,7 @@ impl<R: Read + Seek> TiffReader<R> {
         Ok(Field::Srational(data))
     }
 
-    fn to_sshort_field(&self, buffer: &[u8]) -> Result<Field, Error> {
+    fn to_sshort_field(&self, buffer: &[u8]) -> Result<Field, TiffError> {
         let size: usize = type_size(SSHORT)?;
 
         let mut data: Vec<Sshort> = Vec::<Sshort>::new();
@@ -517,12 +1631,13 @@ impl<R: Read + Seek> TiffReader<R> {
      *                         Methods that convert byte arrays to types                         *
      *********************************************************************************************/
 
-    fn to_double(&self, buffer: &[u8]) -> Result<Double, Error> {
+    fn to_double(&self, buffer: &[u8]) -> Result<Double, TiffError> {
         if buffer.len() != 8 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for float: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 8,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => Double::from_le_bytes([
@@ -533,21 +1648,17 @@ impl<R: Read + Seek> TiffReader<R> {
                 buffer[0], buffer[1], buffer[2], buffer[3], buffer[4], buffer[5], buffer[6],
                 buffer[7],
             ]),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_float(&self, buffer: &[u8]) -> Result<Float, Error> {
+    fn to_float(&self, buffer: &[u8]) -> Result<Float, TiffError> {
         if buffer.len() != 4 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for float: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 4,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => {
@@ -556,21 +1667,17 @@ impl<R: Read + Seek> TiffReader<R> {
             Endianness::BigEndian => {
                 Float::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]])
             }
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_long(&self, buffer: &[u8]) -> Result<Long, Error> {
+    fn to_long(&self, buffer: &[u8]) -> Result<Long, TiffError> {
         if buffer.len() != 4 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for long: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 4,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => {
@@ -579,21 +1686,17 @@ impl<R: Read + Seek> TiffReader<R> {
             Endianness::BigEndian => {
                 Long::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]])
             }
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_rational(&self, buffer: &[u8]) -> Result<Ratio<Long>, Error> {
+    fn to_rational(&self, buffer: &[u8]) -> Result<Ratio<Long>, TiffError> {
         if buffer.len() != 8 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for rational: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 8,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => Ratio::new_raw(
@@ -604,60 +1707,48 @@ impl<R: Read + Seek> TiffReader<R> {
                 Long::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]),
                 Long::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]),
             ),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_sbyte(&self, buffer: &[u8]) -> Result<Sbyte, Error> {
+    fn to_sbyte(&self, buffer: &[u8]) -> Result<Sbyte, TiffError> {
         if buffer.len() != 1 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for sbyte: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 1,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => Sbyte::from_le_bytes([buffer[0]]),
             Endianness::BigEndian => Sbyte::from_be_bytes([buffer[0]]),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_short(&self, buffer: &[u8]) -> Result<Short, Error> {
+    fn to_short(&self, buffer: &[u8]) -> Result<Short, TiffError> {
         // TODO compare to type_size(SHORT)
         if buffer.len() != 2 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for short: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 2,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => Short::from_le_bytes([buffer[0], buffer[1]]),
             Endianness::BigEndian => Short::from_be_bytes([buffer[0], buffer[1]]),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_slong(&self, buffer: &[u8]) -> Result<Slong, Error> {
+    fn to_slong(&self, buffer: &[u8]) -> Result<Slong, TiffError> {
         if buffer.len() != 4 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for slong: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 4,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => {
@@ -666,21 +1757,43 @@ impl<R: Read + Seek> TiffReader<R> {
             Endianness::BigEndian => {
                 Slong::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]])
             }
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
+        })
+    }
+
+    fn to_long8(&self, buffer: &[u8]) -> Result<Long8, TiffError> {
+        let bytes: [u8; 8] = buffer.try_into().map_err(|_| TiffError::TruncatedValue {
+            tag: None,
+            expected: 8,
+            found: buffer.len(),
+        })?;
+        Ok(match self.endianness {
+            Endianness::LittleEndian => Long8::from_le_bytes(bytes),
+            Endianness::BigEndian => Long8::from_be_bytes(bytes),
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_srational(&self, buffer: &[u8]) -> Result<Ratio<Slong>, Error> {
+    fn to_slong8(&self, buffer: &[u8]) -> Result<Slong8, TiffError> {
+        let bytes: [u8; 8] = buffer.try_into().map_err(|_| TiffError::TruncatedValue {
+            tag: None,
+            expected: 8,
+            found: buffer.len(),
+        })?;
+        Ok(match self.endianness {
+            Endianness::LittleEndian => Slong8::from_le_bytes(bytes),
+            Endianness::BigEndian => Slong8::from_be_bytes(bytes),
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
+        })
+    }
+
+    fn to_srational(&self, buffer: &[u8]) -> Result<Ratio<Slong>, TiffError> {
         if buffer.len() != 8 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for srational: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 8,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => Ratio::new_raw(
@@ -691,35 +1804,102 @@ impl<R: Read + Seek> TiffReader<R> {
                 Slong::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]),
                 Slong::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]),
             ),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 
-    fn to_sshort(&self, buffer: &[u8]) -> Result<Sshort, Error> {
+    fn to_sshort(&self, buffer: &[u8]) -> Result<Sshort, TiffError> {
         if buffer.len() != 2 {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("Wrong array size for sshort: {}", buffer.len()),
-            ));
+            return Err(TiffError::TruncatedValue {
+                tag: None,
+                expected: 2,
+                found: buffer.len(),
+            });
         }
         Ok(match self.endianness {
             Endianness::LittleEndian => Sshort::from_le_bytes([buffer[0], buffer[1]]),
             Endianness::BigEndian => Sshort::from_be_bytes([buffer[0], buffer[1]]),
-            Endianness::Uninitialized => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "TiffReader was not initialized correctly before read attempt",
-                ))
-            }
+            Endianness::Uninitialized => return Err(TiffError::Uninitialized),
         })
     }
 }
 
+// TIFF type of the field, as found in the file
+fn field_type(field: &Field) -> u16 {
+    match field {
+        Field::Byte(_) => BYTE,
+        Field::Ascii(..) => ASCII,
+        Field::Utf8(..) => UTF8,
+        Field::Short(_) => SHORT,
+        Field::Long(_) => LONG,
+        Field::Rational(_) => RATIONAL,
+        Field::Sbyte(_) => SBYTE,
+        Field::Undefined(_) => UNDEFINED,
+        Field::Sshort(_) => SSHORT,
+        Field::Slong(_) => SLONG,
+        Field::Srational(_) => SRATIONAL,
+        Field::Float(_) => FLOAT,
+        Field::Double(_) => DOUBLE,
+        Field::Ifd(_) => IFD,
+        Field::Long8(_) => LONG8,
+        Field::Slong8(_) => SLONG8,
+        Field::Ifd8(_) => IFD8,
+        Field::Lazy { type_, .. } => *type_,
+    }
+}
+
+// 64 bit integers do not all fit in a double, these round to the nearest one that does
+fn u64_to_f64(value: u64) -> f64 {
+    let high: u32 = u32::try_from(value >> 32).unwrap_or(u32::MAX);
+    let low: u32 = u32::try_from(value & 0xffff_ffff).unwrap_or(u32::MAX);
+    f64::from(high) * 4_294_967_296.0 + f64::from(low)
+}
+
+fn i64_to_f64(value: i64) -> f64 {
+    let magnitude: f64 = u64_to_f64(value.unsigned_abs());
+    if value < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+// The one value of a field meant to have a single value
+fn scalar<T: Copy>(values: &[T]) -> Result<T, TiffError> {
+    match values {
+        [value] => Ok(*value),
+        _ => Err(TiffError::InvalidData(format!(
+            "Expected a single value, found {}",
+            values.len()
+        ))),
+    }
+}
+
+fn whole(numerator: i64, denominator: i64) -> Result<i64, TiffError> {
+    // Checked, as both zero denominators and i64::MIN / -1 would panic
+    match (
+        numerator.checked_rem(denominator),
+        numerator.checked_div(denominator),
+    ) {
+        (Some(0), Some(quotient)) => Ok(quotient),
+        _ => Err(TiffError::InvalidData(format!(
+            "{numerator}/{denominator} is not a whole number"
+        ))),
+    }
+}
+
+fn not_a_number(field: &Field) -> TiffError {
+    match field {
+        Field::Lazy { .. } => {
+            TiffError::InvalidData("Value was not loaded, see Field::load()".to_string())
+        }
+        _ => TiffError::InvalidData(format!(
+            "Values of type {} are not numbers",
+            field_type(field)
+        )),
+    }
+}
+
 fn to_ascii_field(buffer: &[u8]) -> Field {
     let mut lenght: usize = buffer.len();
 
@@ -731,5 +1911,64 @@ fn to_ascii_field(buffer: &[u8]) -> Field {
             break;
         }
     }
-    Field::Ascii(String::from_utf8(buffer[..lenght].to_vec()).unwrap())
+    Field::Ascii(decode_ascii(&buffer[..lenght]), byte_count(buffer))
+}
+
+// Camera firmware writes all sorts of bytes past 7-bit ASCII in ASCII fields, some UTF-8, most
+// some 8-bit code page. UTF-8 is kept when valid, anything else is taken as Latin-1, which maps
+// every byte to a character and so never loses any.
+fn decode_ascii(buffer: &[u8]) -> String {
+    match std::str::from_utf8(buffer) {
+        Ok(text) => text.to_string(),
+        Err(_) => buffer.iter().map(|byte| char::from(*byte)).collect(),
+    }
+}
+
+fn to_f64<T: Copy + Into<f64>>(value: Ratio<T>) -> Option<f64> {
+    let quotient: f64 = (*value.numer()).into() / (*value.denom()).into();
+    Some(quotient).filter(|quotient| quotient.is_finite())
+}
+
+fn to_utf8_field(buffer: &[u8]) -> Field {
+    // Same as ASCII, except that invalid sequences are replaced rather than trusted
+    let lenght: usize = buffer
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    Field::Utf8(
+        String::from_utf8_lossy(&buffer[..lenght]).into_owned(),
+        byte_count(buffer),
+    )
+}
+
+// Buffers of one byte values are as long as their count, which came from a u32
+fn byte_count(buffer: &[u8]) -> u32 {
+    u32::try_from(buffer.len()).unwrap_or(u32::MAX)
+}
+
+// Counts come straight from the file, so the size may well not fit in a usize
+fn value_size(type_: u16, count: u32) -> Result<usize, TiffError> {
+    let type_size: usize = type_size(type_)?;
+    usize::try_from(count)
+        .ok()
+        .and_then(|count| type_size.checked_mul(count))
+        .ok_or(TiffError::ValueTooLarge {
+            tag: None,
+            type_,
+            count,
+        })
+}
+
+// Sizes come from the file, so allocation failure is an error rather than an abort
+fn new_buffer(size: usize) -> Result<Vec<u8>, TiffError> {
+    let mut buffer: Vec<u8> = Vec::new();
+    buffer.try_reserve_exact(size).map_err(|error| {
+        TiffError::Io(Error::new(
+            ErrorKind::OutOfMemory,
+            format!("Cannot allocate {size} bytes: {error}"),
+        ))
+    })?;
+    // Zeroed, since handing uninitialized memory to Read is undefined behavior
+    buffer.resize(size, 0);
+    Ok(buffer)
 }
@@ -0,0 +1,114 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::{field_type, Field, Ifd, Rational, SRational, TiffError};
+
+// Lookup, type check and decoding in one call, for the common case of wanting a tag's value as a
+// plain Rust type and an error saying what is wrong otherwise: the tag is missing, its values
+// are not loaded, or have a type or count that does not fit.
+impl Ifd {
+    /// Single BYTE, SHORT or LONG value
+    ///
+    /// # Errors
+    ///
+    /// Tag missing or not loaded, any other type, or more or fewer than one value
+    pub fn get_u32(&self, tag: Tag) -> Result<u32, TiffError> {
+        single(tag, &self.get_u32s(tag)?)
+    }
+
+    /// BYTE, SHORT or LONG values
+    ///
+    /// # Errors
+    ///
+    /// Tag missing or not loaded, or any other type
+    pub fn get_u32s(&self, tag: Tag) -> Result<Vec<u32>, TiffError> {
+        match self.lookup(tag)? {
+            Field::Byte(values) => Ok(values.iter().map(|value| u32::from(*value)).collect()),
+            Field::Short(values) => Ok(values.iter().map(|value| u32::from(*value)).collect()),
+            Field::Long(values) => Ok(values.clone()),
+            field => Err(unexpected_type(tag, field)),
+        }
+    }
+
+    /// ASCII or UTF-8 value, several strings separated by NULs, see `Field::strings()`
+    ///
+    /// # Errors
+    ///
+    /// Tag missing or not loaded, or any other type
+    pub fn get_string(&self, tag: Tag) -> Result<String, TiffError> {
+        match self.lookup(tag)? {
+            Field::Ascii(value, _) | Field::Utf8(value, _) => Ok(value.clone()),
+            field => Err(unexpected_type(tag, field)),
+        }
+    }
+
+    /// Single RATIONAL value
+    ///
+    /// # Errors
+    ///
+    /// Tag missing or not loaded, any other type, or more or fewer than one value
+    pub fn get_rational(&self, tag: Tag) -> Result<Rational, TiffError> {
+        let field: &Field = self.lookup(tag)?;
+        single(
+            tag,
+            &field
+                .rationals()
+                .ok_or_else(|| unexpected_type(tag, field))?,
+        )
+    }
+
+    /// Single SRATIONAL value
+    ///
+    /// # Errors
+    ///
+    /// Tag missing or not loaded, any other type, or more or fewer than one value
+    pub fn get_srational(&self, tag: Tag) -> Result<SRational, TiffError> {
+        let field: &Field = self.lookup(tag)?;
+        single(
+            tag,
+            &field
+                .srationals()
+                .ok_or_else(|| unexpected_type(tag, field))?,
+        )
+    }
+
+    fn lookup(&self, tag: Tag) -> Result<&Field, TiffError> {
+        match self.fields.get(&tag) {
+            None => Err(TiffError::MissingTag(tag)),
+            Some(Field::Lazy { .. }) => Err(TiffError::InvalidData(format!(
+                "\"{tag:?}\" was not loaded, see Field::load()"
+            ))),
+            Some(field) => Ok(field),
+        }
+    }
+}
+
+fn single<T: Copy>(tag: Tag, values: &[T]) -> Result<T, TiffError> {
+    match values {
+        [value] => Ok(*value),
+        _ => Err(TiffError::UnexpectedCount {
+            tag,
+            expected: 1,
+            found: values.len(),
+        }),
+    }
+}
+
+fn unexpected_type(tag: Tag, field: &Field) -> TiffError {
+    TiffError::UnexpectedType {
+        tag,
+        type_: field_type(field),
+    }
+}
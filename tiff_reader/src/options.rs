@@ -0,0 +1,198 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use crate::Backend;
+
+// How TiffReader goes about reading a file. Defaults read every value right away, in the order
+// entries are found, and fail on any spec violation.
+#[derive(Clone, Debug)]
+pub struct ReaderOptions {
+    // Size, in bytes, above which out-of-line values are left as Field::Lazy
+    pub(crate) inline_value_limit: usize,
+    pub(crate) sequential: bool,
+    pub(crate) strict: bool,
+    pub(crate) max_ifds: usize,
+    pub(crate) max_ifd_depth: usize,
+    pub(crate) max_fields_per_ifd: usize,
+    pub(crate) max_field_bytes: usize,
+    pub(crate) max_total_bytes: usize,
+    pub(crate) backend: Backend,
+    pub(crate) duplicate_tags: DuplicateTags,
+    pub(crate) odd_ifd_offsets: OddOffsets,
+    pub(crate) odd_value_offsets: OddOffsets,
+}
+
+/// What to do with a tag found more than once in the same IFD
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DuplicateTags {
+    /// Fail the read
+    Error,
+    /// Ignore later occurrences
+    KeepFirst,
+    /// Later occurrences replace earlier ones
+    #[default]
+    KeepLast,
+    /// Keep the first occurrence in `Ifd::fields` and later ones in `Ifd::duplicates`
+    KeepAll,
+}
+
+/// What to do with offsets that are odd, and therefore not on the word boundary the spec requires
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OddOffsets {
+    /// Same as any other spec violation: fail in strict mode, record a warning otherwise
+    #[default]
+    Violation,
+    /// Fail the read, even in lenient mode
+    Error,
+    /// Read on without a warning, even in strict mode
+    Allow,
+}
+
+impl Default for ReaderOptions {
+    fn default() -> ReaderOptions {
+        ReaderOptions {
+            inline_value_limit: usize::MAX,
+            sequential: false,
+            strict: true,
+            max_ifds: usize::MAX,
+            max_ifd_depth: 32,
+            max_fields_per_ifd: usize::MAX,
+            max_field_bytes: usize::MAX,
+            max_total_bytes: usize::MAX,
+            backend: Backend::Auto,
+            duplicate_tags: DuplicateTags::default(),
+            odd_ifd_offsets: OddOffsets::default(),
+            odd_value_offsets: OddOffsets::default(),
+        }
+    }
+}
+
+impl ReaderOptions {
+    #[must_use]
+    pub fn new() -> ReaderOptions {
+        ReaderOptions::default()
+    }
+
+    /// When lazy, values that do not fit in an IFD entry are not read along with the IFD. Fields
+    /// are returned as `Field::Lazy` instead, recording where the value is, and `Field::load()` reads
+    /// them on demand. This saves time and memory on DNGs carrying megabytes of previews, opcode
+    /// lists and XMP the caller may never look at.
+    #[must_use]
+    pub fn lazy(self, lazy: bool) -> ReaderOptions {
+        self.inline_value_limit(if lazy { 0 } else { usize::MAX })
+    }
+
+    /// Values larger than limit bytes are not read along with the IFD and are returned as
+    /// `Field::Lazy`, as in lazy mode, while smaller ones are read right away. This lets callers
+    /// keep the tags they will surely need at hand without paying for large blobs they may not.
+    /// Values that fit in an IFD entry are always read.
+    #[must_use]
+    pub fn inline_value_limit(mut self, limit: usize) -> ReaderOptions {
+        self.inline_value_limit = limit;
+        self
+    }
+
+    /// When sequential, values that do not fit in an IFD entry are read only after the whole IFD
+    /// has been, sorted by offset, instead of as each entry is found. The reader then mostly moves
+    /// forward, which is much cheaper on sources where seeking backwards is expensive, such as
+    /// network streams wrapped in a spooling buffer. Values left lazy stay that way. The reader
+    /// also does not look for the end of the file up front, so a stream is only read as far as
+    /// the metadata goes, see `TiffReader::from_stream()`.
+    #[must_use]
+    pub fn sequential(mut self, sequential: bool) -> ReaderOptions {
+        self.sequential = sequential;
+        self
+    }
+
+    /// When strict, the default, any spec violation fails the read. Otherwise, violations the
+    /// reader can work around, such as empty IFDs, odd offsets or fields without values, are
+    /// recorded as warnings and reading goes on, see `TiffReader::warnings()`. Camera files break
+    /// the spec all the time, so this is often the only way to get anything out of them.
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> ReaderOptions {
+        self.strict = strict;
+        self
+    }
+
+    /// Most IFDs read, counting those found through `SubIFDs`, `ExifIFD` and the like. Limits, all
+    /// unset by default, keep untrusted files from making the reader spend unbounded time or
+    /// memory, say with an IFD claiming a count of four billion rationals.
+    #[must_use]
+    pub fn max_ifds(mut self, max_ifds: usize) -> ReaderOptions {
+        self.max_ifds = max_ifds;
+        self
+    }
+
+    /// Most levels of IFDs found through `SubIFDs`, `ExifIFD` and the like, counting the one they
+    /// start from. Unlike other limits, this one is set by default, to 32, since each level is a
+    /// recursive call and a file nesting IFDs deep enough would overflow the stack. Real files
+    /// hardly go past four.
+    #[must_use]
+    pub fn max_ifd_depth(mut self, max_ifd_depth: usize) -> ReaderOptions {
+        self.max_ifd_depth = max_ifd_depth;
+        self
+    }
+
+    /// Most entries a single IFD may have
+    #[must_use]
+    pub fn max_fields_per_ifd(mut self, max_fields_per_ifd: usize) -> ReaderOptions {
+        self.max_fields_per_ifd = max_fields_per_ifd;
+        self
+    }
+
+    /// Largest value, in bytes, a single field may have
+    #[must_use]
+    pub fn max_field_bytes(mut self, max_field_bytes: usize) -> ReaderOptions {
+        self.max_field_bytes = max_field_bytes;
+        self
+    }
+
+    /// Most bytes read into memory for values overall, including `TiffReader::read_bytes()`
+    #[must_use]
+    pub fn max_total_bytes(mut self, max_total_bytes: usize) -> ReaderOptions {
+        self.max_total_bytes = max_total_bytes;
+        self
+    }
+
+    /// How `TiffReader::open()` reads the file. By default, large files are memory mapped, if the
+    /// "mmap" feature is enabled, and smaller ones go through a `BufReader`.
+    #[must_use]
+    pub fn backend(mut self, backend: Backend) -> ReaderOptions {
+        self.backend = backend;
+        self
+    }
+
+    /// How to deal with tags found more than once in the same IFD, which the spec does not allow.
+    /// The default keeps the last occurrence. Unless failing, each duplicate is also recorded as a
+    /// warning in lenient mode.
+    #[must_use]
+    pub fn duplicate_tags(mut self, duplicate_tags: DuplicateTags) -> ReaderOptions {
+        self.duplicate_tags = duplicate_tags;
+        self
+    }
+
+    /// How to deal with odd offsets to IFDs, the first one in the header and those chained from
+    /// each IFD. Files from several phones have them, and they read fine otherwise.
+    #[must_use]
+    pub fn odd_ifd_offsets(mut self, odd_ifd_offsets: OddOffsets) -> ReaderOptions {
+        self.odd_ifd_offsets = odd_ifd_offsets;
+        self
+    }
+
+    /// How to deal with odd offsets to values that do not fit in an IFD entry
+    #[must_use]
+    pub fn odd_value_offsets(mut self, odd_value_offsets: OddOffsets) -> ReaderOptions {
+        self.odd_value_offsets = odd_value_offsets;
+        self
+    }
+}
@@ -0,0 +1,172 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use crate::Field;
+use data::{Long, Slong};
+use num_rational::Ratio;
+use std::fmt::{Display, Formatter};
+
+// RATIONAL values exactly as stored, two LONGs: numerator and denominator. Nothing is reduced or
+// checked, so 2/4 and 1/2 are different values, and 1/0 is one too. Exposure times such as 1/250
+// are meant to be shown this way, not as 0.004.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rational {
+    pub num: Long,
+    pub den: Long,
+}
+
+// SRATIONAL values, same as Rational with two SLONGs
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SRational {
+    pub num: Slong,
+    pub den: Slong,
+}
+
+impl Rational {
+    #[must_use]
+    pub fn new(num: Long, den: Long) -> Rational {
+        Rational { num, den }
+    }
+
+    /// Same value in lowest terms. Zero denominators are left alone, they have no lowest terms.
+    #[must_use]
+    pub fn reduced(self) -> Rational {
+        if self.den == 0 {
+            return self;
+        }
+        let divisor: Long = gcd(u64::from(self.num), u64::from(self.den))
+            .try_into()
+            .unwrap_or(1);
+        Rational::new(self.num / divisor, self.den / divisor)
+    }
+
+    /// Value as a floating point number, None when the denominator is zero
+    #[must_use]
+    pub fn to_f64(self) -> Option<f64> {
+        (self.den != 0).then(|| f64::from(self.num) / f64::from(self.den))
+    }
+}
+
+impl SRational {
+    #[must_use]
+    pub fn new(num: Slong, den: Slong) -> SRational {
+        SRational { num, den }
+    }
+
+    /// Same value in lowest terms, with a positive denominator. Zero denominators are left alone,
+    /// as are the few values whose lowest terms do not fit in an SLONG, such as -2147483648/-1.
+    #[must_use]
+    pub fn reduced(self) -> SRational {
+        if self.den == 0 {
+            return self;
+        }
+        let (num, den): (i64, i64) = (i64::from(self.num), i64::from(self.den));
+        let divisor: i64 = i64::try_from(gcd(num.unsigned_abs(), den.unsigned_abs())).unwrap_or(1);
+        let sign: i64 = den.signum();
+        match (
+            Slong::try_from(sign * num / divisor),
+            Slong::try_from(sign * den / divisor),
+        ) {
+            (Ok(num), Ok(den)) => SRational::new(num, den),
+            _ => self,
+        }
+    }
+
+    /// Value as a floating point number, None when the denominator is zero
+    #[must_use]
+    pub fn to_f64(self) -> Option<f64> {
+        (self.den != 0).then(|| f64::from(self.num) / f64::from(self.den))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "{}/{}", self.num, self.den)
+    }
+}
+
+impl Display for SRational {
+    fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "{}/{}", self.num, self.den)
+    }
+}
+
+impl From<Ratio<Long>> for Rational {
+    fn from(value: Ratio<Long>) -> Rational {
+        Rational::new(*value.numer(), *value.denom())
+    }
+}
+
+impl From<Ratio<Slong>> for SRational {
+    fn from(value: Ratio<Slong>) -> SRational {
+        SRational::new(*value.numer(), *value.denom())
+    }
+}
+
+impl From<Rational> for Ratio<Long> {
+    fn from(value: Rational) -> Ratio<Long> {
+        Ratio::new_raw(value.num, value.den)
+    }
+}
+
+impl From<SRational> for Ratio<Slong> {
+    fn from(value: SRational) -> Ratio<Slong> {
+        Ratio::new_raw(value.num, value.den)
+    }
+}
+
+impl Field {
+    /// Values of a RATIONAL field, None for fields of any other type
+    #[must_use]
+    pub fn rationals(&self) -> Option<Vec<Rational>> {
+        match self {
+            Field::Rational(values) => Some(values.iter().copied().map(Rational::from).collect()),
+            _ => None,
+        }
+    }
+
+    /// Values of an SRATIONAL field, None for fields of any other type
+    #[must_use]
+    pub fn srationals(&self) -> Option<Vec<SRational>> {
+        match self {
+            Field::Srational(values) => Some(values.iter().copied().map(SRational::from).collect()),
+            _ => None,
+        }
+    }
+
+    /// Value of a RATIONAL field with a single value
+    #[must_use]
+    pub fn as_rational(&self) -> Option<Rational> {
+        match self.rationals()?.as_slice() {
+            [value] => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Value of an SRATIONAL field with a single value
+    #[must_use]
+    pub fn as_srational(&self) -> Option<SRational> {
+        match self.srationals()?.as_slice() {
+            [value] => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+// Euclid's, on u64 so that the absolute value of any SLONG fits
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
@@ -0,0 +1,159 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::io::{Error, ErrorKind, Read, Seek};
+
+use data::Tag;
+
+use crate::{Endianness, Field, Ifd, TiffError, TiffReader};
+
+// Pixels of an uncompressed image, put together from its strips or tiles, one u16 per sample
+// whatever the bits per sample, so callers need not care how the file packs them. Samples are
+// row by row, left to right, and all samples of a pixel together.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawImage {
+    pub width: u32,
+    pub height: u32,
+    // Bits per sample, from 1 to 16
+    pub bpp: u16,
+    pub samples_per_pixel: u16,
+    pub data: Vec<u16>,
+}
+
+impl RawImage {
+    /// Sample of the pixel at row and column, None outside the image
+    #[must_use]
+    pub fn sample(&self, row: u32, column: u32, sample: u16) -> Option<u16> {
+        if row >= self.height || column >= self.width || sample >= self.samples_per_pixel {
+            return None;
+        }
+        let pixel: u64 = u64::from(row) * u64::from(self.width) + u64::from(column);
+        let index: u64 = pixel * u64::from(self.samples_per_pixel) + u64::from(sample);
+        self.data.get(usize::try_from(index).ok()?).copied()
+    }
+}
+
+impl<R: Read + Seek> TiffReader<R> {
+    /// Reads the uncompressed image of ifd, such as `Dng::hires_ifd`, and unpacks its samples,
+    /// honoring `FillOrder`. See `read_uncompressed_image()` for what images can be read.
+    ///
+    /// # Errors
+    ///
+    /// Those of `read_uncompressed_image()`, samples of different sizes or of more than 16 bits,
+    /// and unknown `FillOrder` values
+    pub fn read_raw_image(&mut self, ifd: &Ifd) -> Result<RawImage, TiffError> {
+        let width: u32 = ifd.get_u32(Tag::ImageWidth)?;
+        let height: u32 = ifd.get_u32(Tag::ImageLength)?;
+        // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 1
+        let samples_per_pixel: u16 = match ifd.number(Tag::SamplesPerPixel).unwrap_or(1) {
+            0 => {
+                return Err(TiffError::InvalidData(
+                    "Pixels of zero samples, check SamplesPerPixel".to_string(),
+                ))
+            }
+            samples_per_pixel => u16::try_from(samples_per_pixel).map_err(|_| {
+                TiffError::InvalidData(format!("Invalid SamplesPerPixel {samples_per_pixel}"))
+            })?,
+        };
+        // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 1
+        let bpp: u16 = match ifd.fields.get(&Tag::BitsPerSample) {
+            Some(Field::Short(bits)) if bits.iter().all(|size| Some(size) == bits.first()) => {
+                bits.first().copied().unwrap_or(1)
+            }
+            Some(Field::Short(bits)) => {
+                return Err(TiffError::InvalidData(format!(
+                    "Cannot unpack samples of {bits:?} bits"
+                )))
+            }
+            _ => 1,
+        };
+        if !(1..=16).contains(&bpp) {
+            return Err(TiffError::InvalidData(format!(
+                "Cannot unpack samples of {bpp} bits"
+            )));
+        }
+
+        let mut bytes: Vec<u8> = self.read_uncompressed_image(ifd)?;
+        // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 1, lower column values in the higher-order bits of each byte. 2 is the
+        // other way round, which unpacking takes care of once each byte is turned back.
+        match ifd.number(Tag::FillOrder) {
+            None | Some(1) => {}
+            Some(2) => bytes
+                .iter_mut()
+                .for_each(|byte| *byte = byte.reverse_bits()),
+            Some(fill_order) => {
+                return Err(TiffError::InvalidData(format!(
+                    "Invalid FillOrder {fill_order}"
+                )))
+            }
+        }
+        let row_samples: usize =
+            usize::try_from(u64::from(width) * u64::from(samples_per_pixel))
+                .map_err(|_| TiffError::InvalidData("Image too large to unpack".to_string()))?;
+        let samples: usize = row_samples
+            .checked_mul(usize::try_from(height).unwrap_or(usize::MAX))
+            .ok_or_else(|| TiffError::InvalidData("Image too large to unpack".to_string()))?;
+        let mut data: Vec<u16> = Vec::new();
+        data.try_reserve_exact(samples).map_err(|error| {
+            TiffError::Io(Error::new(
+                ErrorKind::OutOfMemory,
+                format!("Cannot allocate {samples} samples: {error}"),
+            ))
+        })?;
+
+        // Rows are padded to whole bytes, so each is unpacked on its own
+        let row_bytes: usize = (row_samples * usize::from(bpp)).div_ceil(8);
+        for row in bytes
+            .chunks_exact(row_bytes.max(1))
+            .take(usize::try_from(height).unwrap_or(0))
+        {
+            match (bpp, self.endianness) {
+                (8, _) => data.extend(row.iter().map(|byte| u16::from(*byte))),
+                (16, Endianness::LittleEndian) => data.extend(
+                    row.chunks_exact(2)
+                        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]])),
+                ),
+                (16, Endianness::BigEndian) => data.extend(
+                    row.chunks_exact(2)
+                        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]])),
+                ),
+                (16, Endianness::Uninitialized) => return Err(TiffError::Uninitialized),
+                // Any other size, such as 10, 12 or 14 bits, is packed across byte boundaries,
+                // most significant bit first, whatever the byte order
+                _ => unpack(row, bpp, row_samples, &mut data),
+            }
+        }
+        Ok(RawImage {
+            width,
+            height,
+            bpp,
+            samples_per_pixel,
+            data,
+        })
+    }
+}
+
+fn unpack(row: &[u8], bpp: u16, samples: usize, data: &mut Vec<u16>) {
+    let mut buffer: u32 = 0;
+    let mut bits: u16 = 0;
+    let mut bytes = row.iter();
+    for _ in 0..samples {
+        while bits < bpp {
+            buffer = (buffer << 8) | u32::from(*bytes.next().unwrap_or(&0));
+            bits += 8;
+        }
+        bits -= bpp;
+        // Masked to bpp bits, at most 16
+        data.push(u16::try_from((buffer >> bits) & ((1 << bpp) - 1)).unwrap_or(u16::MAX));
+    }
+}
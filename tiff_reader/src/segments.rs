@@ -0,0 +1,256 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::io::{Read, Seek, SeekFrom};
+
+use data::Tag;
+
+use crate::{new_buffer, Compression, Field, Ifd, PlanarConfig, TiffError, TiffReader};
+
+// Where a strip or tile goes in the image. Segments at the bottom and right edges may hold less
+// image than their size says: the last strip can have fewer rows than RowsPerStrip, and edge
+// tiles are padded to full tile size. Putting the image together naively, with every segment
+// taken as stored, corrupts the bottom and right edges, so only width by length pixels of each
+// are image, starting at the top left, and the rest is to be thrown away.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Segment {
+    // Position in StripOffsets or TileOffsets
+    pub index: usize,
+    pub offset: u64,
+    pub byte_count: u64,
+    // Sample the segment holds, always 0 but for PlanarConfiguration 2
+    pub plane: u32,
+    // Image coordinates of the top left pixel
+    pub row: u32,
+    pub column: u32,
+    // Pixels as stored: tile size, or image width by RowsPerStrip
+    pub stored_width: u32,
+    pub stored_length: u32,
+    // Pixels that are part of the image
+    pub width: u32,
+    pub length: u32,
+}
+
+impl Ifd {
+    /// Every strip or tile of the image, with where it goes and how much of it is image
+    ///
+    /// # Errors
+    ///
+    /// Missing image size, offsets or byte counts, zero sized strips or tiles, and more or fewer
+    /// offsets or byte counts than the image needs
+    pub fn segments(&self) -> Result<Vec<Segment>, TiffError> {
+        let width: u32 = self.get_u32(Tag::ImageWidth)?;
+        let length: u32 = self.get_u32(Tag::ImageLength)?;
+        let tiled: bool = self.fields.contains_key(&Tag::TileOffsets);
+        let (offsets_tag, byte_counts_tag, stored_width, stored_length): (Tag, Tag, u32, u32) =
+            if tiled {
+                (
+                    Tag::TileOffsets,
+                    Tag::TileByteCounts,
+                    self.get_u32(Tag::TileWidth)?,
+                    self.get_u32(Tag::TileLength)?,
+                )
+            } else {
+                // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 2**32 − 1, one strip for the whole image
+                let rows_per_strip: u32 = match self.get_u32(Tag::RowsPerStrip) {
+                    Err(TiffError::MissingTag(_)) => u32::MAX,
+                    rows_per_strip => rows_per_strip?,
+                };
+                (
+                    Tag::StripOffsets,
+                    Tag::StripByteCounts,
+                    width,
+                    rows_per_strip.min(length),
+                )
+            };
+        if stored_width == 0 || stored_length == 0 {
+            return Err(TiffError::InvalidData(format!(
+                "Strips or tiles of {stored_width} by {stored_length} pixels"
+            )));
+        }
+
+        let across: u32 = width.div_ceil(stored_width);
+        let down: u32 = length.div_ceil(stored_length);
+        let planes: u32 = match self.planar_config() {
+            Some(PlanarConfig::Planar) => self.number(Tag::SamplesPerPixel).unwrap_or(1),
+            _ => 1,
+        };
+        let per_plane: usize = usize::try_from(u64::from(across) * u64::from(down))
+            .map_err(|_| TiffError::InvalidData(format!("{across} by {down} strips or tiles")))?;
+        let expected: usize = per_plane.saturating_mul(usize::try_from(planes).unwrap_or(1));
+
+        let offsets: Vec<u64> = self.numbers(offsets_tag)?;
+        let byte_counts: Vec<u64> = self.numbers(byte_counts_tag)?;
+        for (tag, found) in [
+            (offsets_tag, offsets.len()),
+            (byte_counts_tag, byte_counts.len()),
+        ] {
+            if found != expected {
+                return Err(TiffError::UnexpectedCount {
+                    tag,
+                    expected: u32::try_from(expected).unwrap_or(u32::MAX),
+                    found,
+                });
+            }
+        }
+
+        let mut segments: Vec<Segment> = Vec::with_capacity(expected);
+        for (index, (offset, byte_count)) in offsets.into_iter().zip(byte_counts).enumerate() {
+            // Planes one after the other, each row by row, left to right
+            let (plane, position): (usize, usize) = (index / per_plane, index % per_plane);
+            let across: usize = usize::try_from(across).unwrap_or(usize::MAX);
+            // All three fit in u32, being at most planes, width and length
+            let plane: u32 = u32::try_from(plane).unwrap_or(u32::MAX);
+            let row: u32 = u32::try_from(position / across).unwrap_or(u32::MAX) * stored_length;
+            let column: u32 = u32::try_from(position % across).unwrap_or(u32::MAX) * stored_width;
+            segments.push(Segment {
+                index,
+                offset,
+                byte_count,
+                plane,
+                row,
+                column,
+                stored_width,
+                stored_length,
+                width: stored_width.min(width - column),
+                length: stored_length.min(length - row),
+            });
+        }
+        Ok(segments)
+    }
+
+    // Offsets and byte counts, which BigTIFF files may store as LONG8
+    fn numbers(&self, tag: Tag) -> Result<Vec<u64>, TiffError> {
+        match self.fields.get(&tag) {
+            Some(Field::Long8(values)) => Ok(values.clone()),
+            _ => Ok(self.get_u32s(tag)?.into_iter().map(u64::from).collect()),
+        }
+    }
+
+    // Bits of a whole pixel, all samples together
+    pub(crate) fn bits_per_pixel(&self) -> u64 {
+        let samples_per_pixel: u64 = u64::from(self.number(Tag::SamplesPerPixel).unwrap_or(1));
+        // A single BitsPerSample applies to every sample
+        match self.fields.get(&Tag::BitsPerSample) {
+            Some(Field::Short(bits)) if bits.len() == 1 => u64::from(bits[0]) * samples_per_pixel,
+            Some(Field::Short(bits)) => bits.iter().map(|bits| u64::from(*bits)).sum(),
+            _ => samples_per_pixel,
+        }
+    }
+}
+
+impl<R: Read + Seek> TiffReader<R> {
+    /// Puts together the uncompressed, chunky image of ifd from its strips or tiles, rows padded
+    /// to whole bytes, as TIFF stores them. Padding rows of the last strip and padding of edge
+    /// tiles are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Those of `Ifd::segments()` and `read_bytes()`, compressed or planar images, pixels of
+    /// zero bits, and strips or tiles with fewer bytes than the image pixels in them need. The
+    /// whole image counts against `ReaderOptions::max_total_bytes()`.
+    pub fn read_uncompressed_image(&mut self, ifd: &Ifd) -> Result<Vec<u8>, TiffError> {
+        match ifd.compression() {
+            None | Some(Compression::Uncompressed) => {}
+            Some(compression) => {
+                return Err(TiffError::InvalidData(format!(
+                    "Cannot put together {compression:?} compressed images"
+                )))
+            }
+        }
+        if let Some(PlanarConfig::Planar) = ifd.planar_config() {
+            return Err(TiffError::InvalidData(
+                "Cannot put together planar images".to_string(),
+            ));
+        }
+
+        // Byte order and file size come from the header, not read yet if ifd came from another
+        // reader
+        if self.header.is_none() {
+            self.process_header()?;
+        }
+        let segments: Vec<Segment> = ifd.segments()?;
+        let bits_per_pixel: u64 = ifd.bits_per_pixel();
+        if bits_per_pixel == 0 {
+            return Err(TiffError::InvalidData(
+                "Pixels of zero bits, check BitsPerSample and SamplesPerPixel".to_string(),
+            ));
+        }
+        let too_large = || TiffError::InvalidData("Image too large to put together".to_string());
+        let row_bytes = |pixels: u32| -> Result<usize, TiffError> {
+            u64::from(pixels)
+                .checked_mul(bits_per_pixel)
+                .and_then(|bits| usize::try_from(bits.div_ceil(8)).ok())
+                .ok_or_else(too_large)
+        };
+
+        let image_row_bytes: usize = row_bytes(ifd.get_u32(Tag::ImageWidth)?)?;
+        let size: usize = usize::try_from(ifd.get_u32(Tag::ImageLength)?)
+            .ok()
+            .and_then(|length| length.checked_mul(image_row_bytes))
+            .ok_or_else(too_large)?;
+
+        // Every segment is checked against the file before anything is allocated, so that a
+        // small file cannot ask for a huge image it does not have the data for
+        let mut layouts: Vec<(Segment, usize, usize, usize)> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            // Tile widths are multiples of 16 and strips start at column 0, so segments start
+            // at whole bytes
+            let start: u64 = u64::from(segment.column)
+                .checked_mul(bits_per_pixel)
+                .ok_or_else(too_large)?;
+            if !start.is_multiple_of(8) {
+                return Err(TiffError::InvalidData(format!(
+                    "Segment {} does not start at a whole byte",
+                    segment.index
+                )));
+            }
+            let first_byte: usize = usize::try_from(start / 8).map_err(|_| too_large())?;
+            let stored_row_bytes: usize = row_bytes(segment.stored_width)?;
+            let copied_row_bytes: usize = row_bytes(segment.width)?;
+            let rows: usize = usize::try_from(segment.length).map_err(|_| too_large())?;
+            // The last strip may be stored with or without its padding rows
+            let needed: usize = stored_row_bytes.checked_mul(rows).ok_or_else(too_large)?;
+            let found: usize = usize::try_from(segment.byte_count).unwrap_or(usize::MAX);
+            if found < needed {
+                return Err(TiffError::TruncatedValue {
+                    tag: None,
+                    expected: needed,
+                    found,
+                });
+            }
+            self.check_range(segment.offset, needed)?;
+            layouts.push((segment, first_byte, stored_row_bytes, copied_row_bytes));
+        }
+
+        self.count_bytes(size)?;
+        let mut image: Vec<u8> = new_buffer(size)?;
+        let mut data: Vec<u8> = Vec::new();
+        for (segment, first_byte, stored_row_bytes, copied_row_bytes) in layouts {
+            // Already counted as part of the image, so read without going through read_bytes()
+            data.resize(
+                stored_row_bytes * usize::try_from(segment.length).unwrap_or(0),
+                0,
+            );
+            self.reader.seek(SeekFrom::Start(segment.offset))?;
+            self.read_to(&mut data)?;
+            let first_row: usize = usize::try_from(segment.row).map_err(|_| too_large())?;
+            for (row, stored) in data.chunks_exact(stored_row_bytes).enumerate() {
+                let destination: usize = (first_row + row) * image_row_bytes + first_byte;
+                image[destination..destination + copied_row_bytes]
+                    .copy_from_slice(&stored[..copied_row_bytes]);
+            }
+        }
+        Ok(image)
+    }
+}
@@ -0,0 +1,156 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+use num_rational::Ratio;
+use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+use serde::Serialize;
+
+use crate::{field_type, Field, Ifd, Issue, Severity, ValidationReport};
+
+// Fields serialize as their type, count and values, rationals as numerator and denominator pairs.
+// Lazy fields have an offset instead of values.
+impl Serialize for Field {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map: S::SerializeMap = serializer.serialize_map(None)?;
+        serialize_field(&mut map, self)?;
+        map.end()
+    }
+}
+
+// IFDs serialize as a list of fields, sorted by tag and each with its tag name, followed by the
+// IFDs they point to
+impl Serialize for Ifd {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map: S::SerializeMap = serializer.serialize_map(None)?;
+        map.serialize_entry("fields", &Entries(self))?;
+        map.serialize_entry("next_ifd_offset", &self.offset)?;
+        if !self.sub_ifds.is_empty() {
+            map.serialize_entry("sub_ifds", &self.sub_ifds)?;
+        }
+        if let Some(exif) = &self.exif {
+            map.serialize_entry("exif", exif)?;
+        }
+        if let Some(gps) = &self.gps {
+            map.serialize_entry("gps", gps)?;
+        }
+        if let Some(interoperability) = &self.interoperability {
+            map.serialize_entry("interoperability", interoperability)?;
+        }
+        map.end()
+    }
+}
+
+// Reports serialize as whether the file is valid and the list of issues, for CI to act on
+impl Serialize for ValidationReport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map: S::SerializeMap = serializer.serialize_map(None)?;
+        map.serialize_entry("valid", &self.is_valid())?;
+        map.serialize_entry("issues", &self.issues)?;
+        map.end()
+    }
+}
+
+// Issues serialize with their error as the message it displays, tag and offset only when known
+impl Serialize for Issue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map: S::SerializeMap = serializer.serialize_map(None)?;
+        map.serialize_entry("severity", &self.severity)?;
+        map.serialize_entry("ifd", &self.ifd)?;
+        if let Some(tag) = &self.tag {
+            map.serialize_entry("tag", tag)?;
+        }
+        if let Some(offset) = &self.offset {
+            map.serialize_entry("offset", offset)?;
+        }
+        map.serialize_entry("message", &self.error.to_string())?;
+        map.end()
+    }
+}
+
+// "error" or "warning"
+impl Serialize for Severity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct Entries<'a>(&'a Ifd);
+
+impl Serialize for Entries<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<(&Tag, &Field)> = self
+            .0
+            .fields
+            .iter()
+            .chain(self.0.duplicates.iter().map(|(tag, field)| (tag, field)))
+            .collect();
+        // Sorting is stable, so duplicates stay after the first occurrence
+        entries.sort_by_key(|(tag, _)| **tag);
+
+        let mut seq: S::SerializeSeq = serializer.serialize_seq(Some(entries.len()))?;
+        for (tag, field) in entries {
+            seq.serialize_element(&Entry(tag, field))?;
+        }
+        seq.end()
+    }
+}
+
+struct Entry<'a>(&'a Tag, &'a Field);
+
+impl Serialize for Entry<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map: S::SerializeMap = serializer.serialize_map(None)?;
+        map.serialize_entry("tag", self.0)?;
+        serialize_field(&mut map, self.1)?;
+        map.end()
+    }
+}
+
+fn serialize_field<M: SerializeMap>(map: &mut M, field: &Field) -> Result<(), M::Error> {
+    map.serialize_entry("type", &field_type(field))?;
+    match field {
+        Field::Ascii(value, count) | Field::Utf8(value, count) => {
+            map.serialize_entry("count", count)?;
+            map.serialize_entry("values", value)
+        }
+        Field::Byte(values) | Field::Undefined(values) => values_entry(map, values),
+        Field::Short(values) => values_entry(map, values),
+        Field::Long(values) | Field::Ifd(values) => values_entry(map, values),
+        Field::Sbyte(values) => values_entry(map, values),
+        Field::Sshort(values) => values_entry(map, values),
+        Field::Slong(values) => values_entry(map, values),
+        Field::Float(values) => values_entry(map, values),
+        Field::Double(values) => values_entry(map, values),
+        Field::Long8(values) | Field::Ifd8(values) => values_entry(map, values),
+        Field::Slong8(values) => values_entry(map, values),
+        Field::Rational(values) => values_entry(map, &pairs(values)),
+        Field::Srational(values) => values_entry(map, &pairs(values)),
+        Field::Lazy { count, offset, .. } => {
+            map.serialize_entry("count", count)?;
+            map.serialize_entry("offset", offset)
+        }
+    }
+}
+
+fn values_entry<M: SerializeMap, T: Serialize>(map: &mut M, values: &[T]) -> Result<(), M::Error> {
+    map.serialize_entry("count", &values.len())?;
+    map.serialize_entry("values", values)
+}
+
+fn pairs<T: Copy>(values: &[Ratio<T>]) -> Vec<(T, T)> {
+    values
+        .iter()
+        .map(|value| (*value.numer(), *value.denom()))
+        .collect()
+}
@@ -0,0 +1,119 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom};
+
+use crate::{ReaderOptions, TiffError, TiffReader};
+
+// TIFF files are full of offsets pointing backwards and forwards, so TiffReader needs Seek. Spool
+// provides it on top of sources that cannot seek, such as standard input or sockets, by keeping
+// in memory everything read so far. Seeking forward reads, and keeps, whatever lies in between.
+pub struct Spool<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    position: u64,
+    exhausted: bool,
+}
+
+impl<R: Read> Spool<R> {
+    pub fn new(reader: R) -> Spool<R> {
+        Spool {
+            reader,
+            buffer: Vec::new(),
+            position: 0,
+            exhausted: false,
+        }
+    }
+
+    // Reads from the underlying reader until at least length bytes are spooled, or there is
+    // nothing left to read.
+    fn fill_to(&mut self, length: u64) -> Result<(), Error> {
+        let mut chunk: [u8; 8192] = [0u8; 8192];
+        while !self.exhausted && self.spooled() < length {
+            match self.reader.read(&mut chunk) {
+                Ok(0) => self.exhausted = true,
+                Ok(bytes_read) => self.buffer.extend_from_slice(&chunk[..bytes_read]),
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+
+    fn spooled(&self) -> u64 {
+        u64::try_from(self.buffer.len()).unwrap_or(u64::MAX)
+    }
+}
+
+impl<R: Read> Read for Spool<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        let length: u64 = u64::try_from(buffer.len()).unwrap_or(u64::MAX);
+        self.fill_to(self.position.saturating_add(length))?;
+
+        // Positions past the end of the spooled data are legal, but there is nothing to read there
+        let start: usize = match usize::try_from(self.position) {
+            Ok(start) if start < self.buffer.len() => start,
+            _ => return Ok(0),
+        };
+        let bytes_read: usize = buffer.len().min(self.buffer.len() - start);
+        buffer[..bytes_read].copy_from_slice(&self.buffer[start..start + bytes_read]);
+        self.position += u64::try_from(bytes_read).unwrap_or(u64::MAX);
+        Ok(bytes_read)
+    }
+}
+
+impl<R: Read> Seek for Spool<R> {
+    fn seek(&mut self, position: SeekFrom) -> Result<u64, Error> {
+        let (base, delta): (u64, i64) = match position {
+            SeekFrom::Start(offset) => (offset, 0),
+            SeekFrom::Current(delta) => (self.position, delta),
+            SeekFrom::End(delta) => {
+                // The only way to know where the end is
+                self.fill_to(u64::MAX)?;
+                (self.spooled(), delta)
+            }
+        };
+        self.position = base.checked_add_signed(delta).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "Invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+impl<R: Read> TiffReader<Spool<R>> {
+    /// Reads from a source that cannot seek, such as a pipe or a socket, in a single pass as far
+    /// as possible: sequential mode, on top of a `Spool`. Only values that appear in the stream
+    /// before the IFDs pointing to them make the reader go back, and the spool keeps those.
+    ///
+    /// # Errors
+    ///
+    /// Only those caused by the underlying reader
+    pub fn from_stream(reader: R) -> Result<TiffReader<Spool<R>>, TiffError> {
+        TiffReader::from_stream_with_options(reader, ReaderOptions::default())
+    }
+
+    /// Same as `from_stream()`, with sequential mode forced on top of the given options
+    ///
+    /// # Errors
+    ///
+    /// Only those caused by the underlying reader
+    pub fn from_stream_with_options(
+        reader: R,
+        options: ReaderOptions,
+    ) -> Result<TiffReader<Spool<R>>, TiffError> {
+        TiffReader::with_options(Spool::new(reader), options.sequential(true))
+    }
+}
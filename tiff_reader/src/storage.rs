@@ -0,0 +1,154 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+
+use crate::{u64_to_f64, Dng, Field, Ifd, TiffError};
+
+// Where the bytes of a file go: image data of each image, full resolution raw data and previews
+// alike, and everything else, which is mostly metadata. Helps deciding whether recompressing or
+// stripping previews is worth it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorageReport {
+    pub file_size: u64,
+    pub images: Vec<ImageStorage>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageStorage {
+    // Where the image is, such as "ifd0", "hires_ifd" or "sub_ifds[1]"
+    pub name: String,
+    // True for the full resolution raw image, false for previews and thumbnails
+    pub raw: bool,
+    pub compression: u32,
+    pub width: u32,
+    pub length: u32,
+    // Sum of all strip or tile byte counts
+    pub compressed_bytes: u64,
+    // What the image would take with no compression, rows padded to whole bytes
+    pub uncompressed_bytes: u64,
+}
+
+impl ImageStorage {
+    /// Uncompressed over compressed size, None for images with no data
+    #[must_use]
+    pub fn ratio(&self) -> Option<f64> {
+        if self.compressed_bytes == 0 {
+            return None;
+        }
+        Some(u64_to_f64(self.uncompressed_bytes) / u64_to_f64(self.compressed_bytes))
+    }
+}
+
+impl StorageReport {
+    /// Bytes of image data of the full resolution raw image
+    #[must_use]
+    pub fn raw_bytes(&self) -> u64 {
+        self.images
+            .iter()
+            .filter(|image| image.raw)
+            .fold(0, |sum, image| sum.saturating_add(image.compressed_bytes))
+    }
+
+    /// Bytes of image data of previews and thumbnails
+    #[must_use]
+    pub fn preview_bytes(&self) -> u64 {
+        self.images
+            .iter()
+            .filter(|image| !image.raw)
+            .fold(0, |sum, image| sum.saturating_add(image.compressed_bytes))
+    }
+
+    /// Bytes that are not image data: headers, IFDs, metadata values and padding
+    #[must_use]
+    pub fn other_bytes(&self) -> u64 {
+        self.file_size
+            .saturating_sub(self.raw_bytes())
+            .saturating_sub(self.preview_bytes())
+    }
+}
+
+impl Dng {
+    /// Where the bytes of the file go, given its size
+    ///
+    /// # Errors
+    ///
+    /// Byte counts or image sizes too large to add up, which only hostile files have
+    pub fn storage_report(&self, file_size: u64) -> Result<StorageReport, TiffError> {
+        let mut images: Vec<ImageStorage> = Vec::new();
+        for (name, ifd, raw) in self.images() {
+            images.extend(image_storage(&name, ifd, raw)?);
+        }
+        Ok(StorageReport { file_size, images })
+    }
+
+    // Every IFD that may hold an image, named as in ImageStorage, and whether it is the raw one
+    pub(crate) fn images(&self) -> Vec<(String, &Ifd, bool)> {
+        let mut images: Vec<(String, &Ifd, bool)> = vec![
+            ("ifd0".to_string(), &self.ifd0, false),
+            ("hires_ifd".to_string(), &self.hires_ifd, true),
+        ];
+        for (index, ifd) in self.ifd0.sub_ifds.iter().enumerate() {
+            // Positions as in the file, where hires_ifd was taken out from
+            let position: usize = if index < self.hires_index {
+                index
+            } else {
+                index + 1
+            };
+            images.push((format!("sub_ifds[{position}]"), ifd, false));
+        }
+        images
+    }
+}
+
+// None for IFDs without image data
+fn image_storage(name: &str, ifd: &Ifd, raw: bool) -> Result<Option<ImageStorage>, TiffError> {
+    let Some(byte_counts) = ifd
+        .fields
+        .get(&Tag::StripByteCounts)
+        .or_else(|| ifd.fields.get(&Tag::TileByteCounts))
+    else {
+        return Ok(None);
+    };
+    let too_large = || TiffError::InvalidData(format!("Image data of {name} is too large"));
+    let counts: Vec<u64> = match byte_counts {
+        Field::Short(counts) => counts.iter().map(|count| u64::from(*count)).collect(),
+        Field::Long(counts) => counts.iter().map(|count| u64::from(*count)).collect(),
+        Field::Long8(counts) => counts.clone(),
+        _ => return Ok(None),
+    };
+    let compressed_bytes: u64 = counts
+        .into_iter()
+        .try_fold(0u64, u64::checked_add)
+        .ok_or_else(too_large)?;
+
+    let (Some(width), Some(length)) = (ifd.number(Tag::ImageWidth), ifd.number(Tag::ImageLength))
+    else {
+        return Ok(None);
+    };
+    let uncompressed_bytes: u64 = u64::from(width)
+        .checked_mul(ifd.bits_per_pixel())
+        .map(|bits| bits.div_ceil(8))
+        .and_then(|row_bytes| row_bytes.checked_mul(u64::from(length)))
+        .ok_or_else(too_large)?;
+
+    Ok(Some(ImageStorage {
+        name: name.to_string(),
+        raw,
+        compression: ifd.number(Tag::Compression).unwrap_or(1),
+        width,
+        length,
+        compressed_bytes,
+        uncompressed_bytes,
+    }))
+}
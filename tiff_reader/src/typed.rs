@@ -0,0 +1,162 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use crate::{Field, Ifd, Matrix3, Rational, SRational};
+use data::Tag;
+
+// Values of well known tags decoded into the types their meaning calls for, for applications to
+// match on instead of digging through generic arrays. Only tags whose decoding is unambiguous are
+// covered; anything else is still available as a plain Field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedField {
+    ImageWidth(u32),
+    ImageLength(u32),
+    BitsPerSample(Vec<u32>),
+    Compression(u32),
+    PhotometricInterpretation(u32),
+    Make(String),
+    Model(String),
+    Orientation(u32),
+    SamplesPerPixel(u32),
+    Software(String),
+    DateTime(String),
+    Artist(String),
+    Copyright(String),
+    CFARepeatPatternDim(u32, u32),
+    ExposureTime(Rational),
+    FNumber(Rational),
+    ISOSpeedRatings(u32),
+    DateTimeOriginal(String),
+    ExposureBiasValue(SRational),
+    FocalLength(Rational),
+    LensModel(String),
+    BodySerialNumber(String),
+    DNGVersion([u8; 4]),
+    UniqueCameraModel(String),
+    BlackLevel(Vec<f64>),
+    WhiteLevel(Vec<u32>),
+    DefaultCropOrigin(f64, f64),
+    DefaultCropSize(f64, f64),
+    ColorMatrix1(Matrix3),
+    ColorMatrix2(Matrix3),
+    CameraCalibration1(Matrix3),
+    CameraCalibration2(Matrix3),
+    ForwardMatrix1(Matrix3),
+    ForwardMatrix2(Matrix3),
+    AsShotNeutral(Vec<f64>),
+    AsShotWhiteXY(f64, f64),
+    BaselineExposure(f64),
+    CalibrationIlluminant1(u32),
+    CalibrationIlluminant2(u32),
+    LensInfo([f64; 4]),
+    RawDataUniqueID([u8; 16]),
+}
+
+impl Field {
+    /// Value decoded for the given tag, None for tags not covered by `TypedField`, or values that
+    /// do not have the type or count the spec calls for
+    #[must_use]
+    pub fn typed(&self, tag: Tag) -> Option<TypedField> {
+        Some(match tag {
+            Tag::ImageWidth => TypedField::ImageWidth(self.u32()?),
+            Tag::ImageLength => TypedField::ImageLength(self.u32()?),
+            Tag::BitsPerSample => TypedField::BitsPerSample(self.u32s()?),
+            Tag::Compression => TypedField::Compression(self.u32()?),
+            Tag::PhotometricInterpretation => TypedField::PhotometricInterpretation(self.u32()?),
+            Tag::Make => TypedField::Make(self.text()?),
+            Tag::Model => TypedField::Model(self.text()?),
+            Tag::Orientation => TypedField::Orientation(self.u32()?),
+            Tag::SamplesPerPixel => TypedField::SamplesPerPixel(self.u32()?),
+            Tag::Software => TypedField::Software(self.text()?),
+            Tag::DateTime => TypedField::DateTime(self.text()?),
+            Tag::Artist => TypedField::Artist(self.text()?),
+            Tag::Copyright => TypedField::Copyright(self.text()?),
+            Tag::CFARepeatPatternDim => match self.u32s()?.as_slice() {
+                [rows, columns] => TypedField::CFARepeatPatternDim(*rows, *columns),
+                _ => return None,
+            },
+            Tag::ExposureTime => TypedField::ExposureTime(self.as_rational()?),
+            Tag::FNumber => TypedField::FNumber(self.as_rational()?),
+            // Several values are allowed, the first one is the one that matters
+            Tag::ISOSpeedRatings => TypedField::ISOSpeedRatings(*self.u32s()?.first()?),
+            Tag::DateTimeOriginal => TypedField::DateTimeOriginal(self.text()?),
+            Tag::ExposureBiasValue => TypedField::ExposureBiasValue(self.as_srational()?),
+            Tag::FocalLength => TypedField::FocalLength(self.as_rational()?),
+            Tag::LensModel => TypedField::LensModel(self.text()?),
+            Tag::BodySerialNumber => TypedField::BodySerialNumber(self.text()?),
+            Tag::DNGVersion => TypedField::DNGVersion(self.bytes()?),
+            Tag::UniqueCameraModel => TypedField::UniqueCameraModel(self.text()?),
+            Tag::BlackLevel => TypedField::BlackLevel(self.to_f64s()?),
+            Tag::WhiteLevel => TypedField::WhiteLevel(self.u32s()?),
+            Tag::DefaultCropOrigin => {
+                let [x, y]: [f64; 2] = self.to_f64s()?.try_into().ok()?;
+                TypedField::DefaultCropOrigin(x, y)
+            }
+            Tag::DefaultCropSize => {
+                let [width, length]: [f64; 2] = self.to_f64s()?.try_into().ok()?;
+                TypedField::DefaultCropSize(width, length)
+            }
+            Tag::ColorMatrix1 => TypedField::ColorMatrix1(self.as_matrix3x3()?),
+            Tag::ColorMatrix2 => TypedField::ColorMatrix2(self.as_matrix3x3()?),
+            Tag::CameraCalibration1 => TypedField::CameraCalibration1(self.as_matrix3x3()?),
+            Tag::CameraCalibration2 => TypedField::CameraCalibration2(self.as_matrix3x3()?),
+            Tag::ForwardMatrix1 => TypedField::ForwardMatrix1(self.as_matrix3x3()?),
+            Tag::ForwardMatrix2 => TypedField::ForwardMatrix2(self.as_matrix3x3()?),
+            Tag::AsShotNeutral => TypedField::AsShotNeutral(self.to_f64s()?),
+            Tag::AsShotWhiteXY => {
+                let [x, y]: [f64; 2] = self.to_f64s()?.try_into().ok()?;
+                TypedField::AsShotWhiteXY(x, y)
+            }
+            Tag::BaselineExposure => TypedField::BaselineExposure(self.as_scalar_f64().ok()?),
+            Tag::CalibrationIlluminant1 => TypedField::CalibrationIlluminant1(self.u32()?),
+            Tag::CalibrationIlluminant2 => TypedField::CalibrationIlluminant2(self.u32()?),
+            Tag::LensInfo => TypedField::LensInfo(self.to_f64s()?.try_into().ok()?),
+            Tag::RawDataUniqueID => TypedField::RawDataUniqueID(self.bytes()?),
+            _ => return None,
+        })
+    }
+
+    fn u32(&self) -> Option<u32> {
+        u32::try_from(self.as_scalar_i64().ok()?).ok()
+    }
+
+    fn u32s(&self) -> Option<Vec<u32>> {
+        match self {
+            Field::Short(values) => Some(values.iter().map(|value| u32::from(*value)).collect()),
+            Field::Long(values) => Some(values.clone()),
+            _ => None,
+        }
+    }
+
+    fn text(&self) -> Option<String> {
+        match self {
+            Field::Ascii(value, _) | Field::Utf8(value, _) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    fn bytes<const N: usize>(&self) -> Option<[u8; N]> {
+        match self {
+            Field::Byte(values) | Field::Undefined(values) => values.as_slice().try_into().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl Ifd {
+    /// Value of the given tag decoded, see `Field::typed()`
+    #[must_use]
+    pub fn typed(&self, tag: Tag) -> Option<TypedField> {
+        self.fields.get(&tag)?.typed(tag)
+    }
+}
@@ -0,0 +1,434 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::{Tag, TagInfo, ValueCount, BYTE, UNDEFINED};
+
+use crate::{field_type, Dng, Field, Ifd, Photometric, SubFileType, TiffError};
+use std::fmt::{Display, Formatter};
+
+// Newest DNG version this reader is written against. Files that need a newer reader to be read
+// correctly, according to DNGBackwardVersion, may hold data it does not know about.
+const DNG_VERSION: [u8; 4] = [1, 7, 1, 0];
+
+// Outcome of checking a whole DNG file, along the lines of Adobe's dng_validate: every problem
+// found, with where it was found and how serious it is, for tools to act on
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Debug)]
+pub struct Issue {
+    pub severity: Severity,
+    // Path of the IFD the problem is in, such as "ifd0", "hires_ifd/exif" or "sub_ifds[2]", named
+    // as in ImageStorage, empty for the file as a whole or when the reader did not say
+    pub ifd: String,
+    // Field the problem is in, if any
+    pub tag: Option<Tag>,
+    // Where the IFD the problem is in starts in the file, if known
+    pub offset: Option<u64>,
+    pub error: TiffError,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Severity {
+    // Breaks a requirement of the specs, readers may well get the file wrong
+    Error,
+    // Goes against a recommendation, or was let through while reading in lenient mode
+    Warning,
+}
+
+impl Ifd {
+    /// Fields of this IFD whose type or count the specs do not allow for their tag, according to
+    /// the tag registry, see `Tag::info()`. Nothing fails: callers decide what to make of the
+    /// violations. Tags the registry does not know about are not checked, neither are the IFDs
+    /// this one points to.
+    #[must_use]
+    pub fn validate(&self) -> Vec<TiffError> {
+        let samples_per_pixel: u32 = self.number(Tag::SamplesPerPixel).unwrap_or(1);
+
+        let mut tags: Vec<&Tag> = self.fields.keys().collect();
+        tags.sort();
+
+        let mut violations: Vec<TiffError> = Vec::new();
+        for tag in tags {
+            let (Some(info), Some(field)) = (tag.info(), self.fields.get(tag)) else {
+                continue;
+            };
+            if !allows_type(&info, field) {
+                violations.push(TiffError::UnexpectedType {
+                    tag: *tag,
+                    type_: field_type(field),
+                });
+                continue;
+            }
+            let expected: Option<u32> = match info.count {
+                ValueCount::Fixed(count) => Some(count),
+                ValueCount::PerSample => Some(samples_per_pixel),
+                ValueCount::Variable => None,
+            };
+            if let (Some(expected), Some(found)) = (expected, count(field)) {
+                if u32::try_from(found).ok() != Some(expected) {
+                    violations.push(TiffError::UnexpectedCount {
+                        tag: *tag,
+                        expected,
+                        found,
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+// Values read as UNDEFINED end up as Field::Byte, so either type is as good as the other
+fn allows_type(info: &TagInfo, field: &Field) -> bool {
+    match field {
+        Field::Byte(_) => info.allows_type(BYTE) || info.allows_type(UNDEFINED),
+        _ => info.allows_type(field_type(field)),
+    }
+}
+
+// Text is not counted, as the terminating NUL and any padding after it are gone by now
+fn count(field: &Field) -> Option<usize> {
+    Some(match field {
+        Field::Ascii(..) | Field::Utf8(..) => return None,
+        Field::Byte(values) | Field::Undefined(values) => values.len(),
+        Field::Short(values) => values.len(),
+        Field::Long(values) | Field::Ifd(values) => values.len(),
+        Field::Rational(values) => values.len(),
+        Field::Sbyte(values) => values.len(),
+        Field::Sshort(values) => values.len(),
+        Field::Slong(values) => values.len(),
+        Field::Srational(values) => values.len(),
+        Field::Float(values) => values.len(),
+        Field::Double(values) => values.len(),
+        Field::Long8(values) | Field::Ifd8(values) => values.len(),
+        Field::Slong8(values) => values.len(),
+        Field::Lazy { count, .. } => usize::try_from(*count).unwrap_or(usize::MAX),
+    })
+}
+
+impl ValidationReport {
+    /// True when there are no errors, warnings or not
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    // Position is that of the IFD, see Ifd::position
+    fn error(&mut self, ifd: &str, position: u64, error: TiffError) {
+        self.push(Severity::Error, ifd, position, error);
+    }
+
+    fn warning(&mut self, ifd: &str, position: u64, error: TiffError) {
+        self.push(Severity::Warning, ifd, position, error);
+    }
+
+    fn push(&mut self, severity: Severity, ifd: &str, position: u64, error: TiffError) {
+        self.issues.push(Issue {
+            severity,
+            ifd: ifd.to_string(),
+            tag: error.tag(),
+            offset: Some(position).filter(|position| *position != 0),
+            error,
+        });
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(formatter, "error"),
+            Severity::Warning => write!(formatter, "warning"),
+        }
+    }
+}
+
+impl Dng {
+    /// Checks the whole file against the DNG specification: fields of every IFD, tags DNG
+    /// requires, `DNGVersion` against `DNGBackwardVersion`, color matrices against the number of
+    /// color planes, raw image digests, and the layout of IFDs. Spec violations let through while
+    /// reading, see `ReaderOptions::strict()`, are reported as warnings.
+    #[must_use]
+    pub fn validate(&self) -> ValidationReport {
+        let mut report: ValidationReport = ValidationReport::default();
+        for warning in &self.warnings {
+            report.warning("", 0, warning.copy());
+        }
+
+        let mut ifds: Vec<(String, &Ifd)> = Vec::new();
+        for (name, ifd, _) in self.images() {
+            // The other SubIFDs of IFD0 are images of their own
+            ifd_tree(&name, ifd, name != "ifd0", &mut ifds);
+        }
+        for (path, ifd) in ifds {
+            for error in ifd.validate() {
+                report.error(&path, ifd.position, error);
+            }
+        }
+
+        self.validate_version(&mut report);
+        self.validate_layout(&mut report);
+        self.validate_raw_image(&mut report);
+        self.validate_color(&mut report);
+
+        // Not required, but without one nothing can tell whether the raw data is intact
+        if ![Tag::NewRawImageDigest, Tag::RawImageDigest]
+            .iter()
+            .any(|tag| self.ifd0.fields.contains_key(tag))
+        {
+            report.warning(
+                "ifd0",
+                self.ifd0.position,
+                TiffError::InvalidData("No NewRawImageDigest or RawImageDigest".to_string()),
+            );
+        }
+        report
+    }
+
+    fn validate_version(&self, report: &mut ValidationReport) {
+        let version: [u8; 4] = match self.ifd0.fields.get(&Tag::DNGVersion) {
+            Some(Field::Byte(version)) if version.len() == 4 => {
+                [version[0], version[1], version[2], version[3]]
+            }
+            Some(_) => return,
+            None => {
+                report.error(
+                    "ifd0",
+                    self.ifd0.position,
+                    TiffError::MissingTag(Tag::DNGVersion),
+                );
+                return;
+            }
+        };
+        // 𝐷𝑒𝑓𝑎𝑢𝑙𝑡: 𝐷𝑁𝐺𝑉𝑒𝑟𝑠𝑖𝑜𝑛 𝑤𝑖𝑡ℎ 𝑡ℎ𝑒 𝑙𝑎𝑠𝑡 𝑡𝑤𝑜 𝑏𝑦𝑡𝑒𝑠 𝑠𝑒𝑡 𝑡𝑜 𝑧𝑒𝑟𝑜
+        let backward_version: [u8; 4] = match self.ifd0.fields.get(&Tag::DNGBackwardVersion) {
+            Some(Field::Byte(backward)) if backward.len() == 4 => {
+                [backward[0], backward[1], backward[2], backward[3]]
+            }
+            Some(_) => return,
+            None => [version[0], version[1], 0, 0],
+        };
+        if backward_version > version {
+            report.error(
+                "ifd0",
+                self.ifd0.position,
+                TiffError::InvalidData(format!(
+                    "DNGBackwardVersion {} is newer than DNGVersion {}",
+                    dotted(backward_version),
+                    dotted(version)
+                )),
+            );
+        }
+        if backward_version > DNG_VERSION {
+            report.warning(
+                "ifd0",
+                self.ifd0.position,
+                TiffError::InvalidData(format!(
+                    "File needs a DNG {} reader, this one knows DNG {}",
+                    dotted(backward_version),
+                    dotted(DNG_VERSION)
+                )),
+            );
+        }
+        if self.ifd0.ascii(Tag::UniqueCameraModel).is_none() {
+            report.error(
+                "ifd0",
+                self.ifd0.position,
+                TiffError::MissingTag(Tag::UniqueCameraModel),
+            );
+        }
+    }
+
+    fn validate_layout(&self, report: &mut ValidationReport) {
+        // TIFF/EP recommends a thumbnail in IFD0
+        if self.ifd0.sub_file_type() != Some(SubFileType::Preview) {
+            report.warning(
+                "ifd0",
+                self.ifd0.position,
+                TiffError::InvalidData("IFD0 is not flagged as a preview".to_string()),
+            );
+        }
+        if self.hires_ifd.sub_file_type().unwrap_or(SubFileType::Main) != SubFileType::Main {
+            report.error(
+                "hires_ifd",
+                self.hires_ifd.position,
+                TiffError::InvalidData("Raw IFD is not flagged as the main image".to_string()),
+            );
+        }
+        let main_images: usize = self.sub_ifds_of_type(SubFileType::Main).len();
+        if main_images > 1 {
+            report.warning(
+                "",
+                0,
+                TiffError::InvalidData(format!(
+                    "{main_images} IFDs are flagged as the main image, only one can be the raw data"
+                )),
+            );
+        }
+    }
+
+    fn validate_raw_image(&self, report: &mut ValidationReport) {
+        let ifd: &Ifd = &self.hires_ifd;
+        let mut required: Vec<Tag> = vec![
+            Tag::ImageWidth,
+            Tag::ImageLength,
+            Tag::BitsPerSample,
+            Tag::Compression,
+            Tag::PhotometricInterpretation,
+        ];
+        if ifd.photometric() == Some(Photometric::Cfa) {
+            required.extend([Tag::CFARepeatPatternDim, Tag::CFAPattern]);
+        }
+        for tag in required {
+            if !ifd.fields.contains_key(&tag) {
+                report.error(
+                    "hires_ifd",
+                    self.hires_ifd.position,
+                    TiffError::MissingTag(tag),
+                );
+            }
+        }
+
+        let strips: bool = [Tag::StripOffsets, Tag::StripByteCounts]
+            .iter()
+            .all(|tag| ifd.fields.contains_key(tag));
+        let tiles: bool = [Tag::TileOffsets, Tag::TileByteCounts]
+            .iter()
+            .all(|tag| ifd.fields.contains_key(tag));
+        if !strips && !tiles {
+            report.error(
+                "hires_ifd",
+                self.hires_ifd.position,
+                TiffError::InvalidData(
+                    "Raw image has neither strip nor tile offsets and byte counts".to_string(),
+                ),
+            );
+        }
+    }
+
+    fn validate_color(&self, report: &mut ValidationReport) {
+        let Some(color_planes) = self.color_planes() else {
+            return;
+        };
+        if color_planes > 1 && !self.ifd0.fields.contains_key(&Tag::ColorMatrix1) {
+            report.error(
+                "ifd0",
+                self.ifd0.position,
+                TiffError::MissingTag(Tag::ColorMatrix1),
+            );
+        }
+
+        // Values each tag needs, for color_planes planes
+        let expected: [(&[Tag], usize); 6] = [
+            (
+                &[Tag::ColorMatrix1, Tag::ColorMatrix2, Tag::ColorMatrix3],
+                3 * color_planes,
+            ),
+            (
+                &[
+                    Tag::ForwardMatrix1,
+                    Tag::ForwardMatrix2,
+                    Tag::ForwardMatrix3,
+                ],
+                3 * color_planes,
+            ),
+            (
+                &[
+                    Tag::ReductionMatrix1,
+                    Tag::ReductionMatrix2,
+                    Tag::ReductionMatrix3,
+                ],
+                3 * color_planes,
+            ),
+            (
+                &[
+                    Tag::CameraCalibration1,
+                    Tag::CameraCalibration2,
+                    Tag::CameraCalibration3,
+                ],
+                color_planes * color_planes,
+            ),
+            (&[Tag::AnalogBalance], color_planes),
+            (&[Tag::AsShotNeutral], color_planes),
+        ];
+        for (tags, expected) in expected {
+            for tag in tags {
+                let Some(found) = self.ifd0.fields.get(tag).and_then(count) else {
+                    continue;
+                };
+                if found != expected {
+                    report.error(
+                        "ifd0",
+                        self.ifd0.position,
+                        TiffError::UnexpectedCount {
+                            tag: *tag,
+                            expected: u32::try_from(expected).unwrap_or(u32::MAX),
+                            found,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    // Color planes of the raw image: one per color of its CFA, or per sample without one
+    fn color_planes(&self) -> Option<usize> {
+        match self.hires_ifd.photometric() {
+            Some(Photometric::Cfa) => Some(self.cfa_pattern()?.plane_colors.len()),
+            _ => usize::try_from(self.hires_ifd.number(Tag::SamplesPerPixel).unwrap_or(1)).ok(),
+        }
+    }
+}
+
+// The IFD and those it points to, recursively, each with its path. SubIFDs are left out when
+// they are listed some other way.
+fn ifd_tree<'a>(path: &str, ifd: &'a Ifd, sub_ifds: bool, found: &mut Vec<(String, &'a Ifd)>) {
+    let linked: [(&str, Option<&Ifd>); 3] = [
+        ("exif", ifd.exif.as_deref()),
+        ("gps", ifd.gps.as_deref()),
+        ("interoperability", ifd.interoperability.as_deref()),
+    ];
+    found.push((path.to_string(), ifd));
+    if sub_ifds {
+        for (index, sub_ifd) in ifd.sub_ifds.iter().enumerate() {
+            ifd_tree(&format!("{path}/sub_ifds[{index}]"), sub_ifd, true, found);
+        }
+    }
+    for (name, linked) in linked {
+        if let Some(linked) = linked {
+            ifd_tree(&format!("{path}/{name}"), linked, true, found);
+        }
+    }
+}
+
+fn dotted(version: [u8; 4]) -> String {
+    version
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(".")
+}
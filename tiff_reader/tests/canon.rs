@@ -0,0 +1,272 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::io::Cursor;
+
+use tiff_reader::{CameraSettings, CanonMakerNote, ReaderOptions, ShotInfo, TiffError, TiffReader};
+
+const ASCII: u16 = 2;
+const SHORT: u16 = 3;
+const LONG: u16 = 4;
+const UNDEFINED: u16 = 7;
+const MAKE: u16 = 271;
+const EXIF_IFD: u16 = 34665;
+const MAKER_NOTE: u16 = 37500;
+
+enum Value {
+    // Bytes in the byte order of the file, in the entry if they fit or else after the IFD
+    Bytes(Vec<u8>),
+    // Offset of a value already in the file
+    At(u32),
+}
+
+// TIFF put together IFD by IFD, each after the last
+struct File {
+    big_endian: bool,
+    bytes: Vec<u8>,
+}
+
+impl File {
+    fn new(big_endian: bool) -> File {
+        let mut file: File = File {
+            big_endian,
+            bytes: if big_endian { b"MM" } else { b"II" }.to_vec(),
+        };
+        let magic: Vec<u8> = file.shorts(&[42]);
+        file.bytes.extend(magic);
+        file.bytes.extend([0; 4]);
+        file
+    }
+
+    fn shorts(&self, values: &[u16]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| {
+                if self.big_endian {
+                    value.to_be_bytes()
+                } else {
+                    value.to_le_bytes()
+                }
+            })
+            .collect()
+    }
+
+    fn long(&self, value: u32) -> Vec<u8> {
+        if self.big_endian {
+            value.to_be_bytes().to_vec()
+        } else {
+            value.to_le_bytes().to_vec()
+        }
+    }
+
+    // Appends an IFD, on a word boundary and followed by the values that do not fit in its
+    // entries, each a tag, type, count and value. Returns where the IFD starts.
+    fn ifd(&mut self, entries: &[(u16, u16, u32, Value)]) -> u32 {
+        self.bytes.resize(self.bytes.len().next_multiple_of(2), 0);
+        let offset: u32 = size(&self.bytes);
+        let values_offset: u32 = offset + 2 + 12 * size(entries) + 4;
+        let mut values: Vec<u8> = Vec::new();
+
+        let mut ifd: Vec<u8> = self.shorts(&[u16::try_from(entries.len()).unwrap()]);
+        for (tag, type_, count, value) in entries {
+            ifd.extend(self.shorts(&[*tag, *type_]));
+            ifd.extend(self.long(*count));
+            match value {
+                Value::Bytes(bytes) if bytes.len() > 4 => {
+                    ifd.extend(self.long(values_offset + size(&values)));
+                    values.extend(bytes);
+                    values.resize(values.len().next_multiple_of(2), 0);
+                }
+                Value::Bytes(bytes) => {
+                    let mut bytes: Vec<u8> = bytes.clone();
+                    bytes.resize(4, 0);
+                    ifd.extend(bytes);
+                }
+                Value::At(at) => ifd.extend(self.long(*at)),
+            }
+        }
+        ifd.extend([0; 4]);
+        self.bytes.extend(ifd);
+        self.bytes.extend(values);
+        offset
+    }
+
+    // Appends IFD0 and points the header to it
+    fn ifd0(mut self, entries: &[(u16, u16, u32, Value)]) -> Vec<u8> {
+        let offset: u32 = self.ifd(entries);
+        let offset: Vec<u8> = self.long(offset);
+        self.bytes[4..8].copy_from_slice(&offset);
+        self.bytes
+    }
+}
+
+fn size<T>(values: &[T]) -> u32 {
+    u32::try_from(values.len()).unwrap()
+}
+
+// File of the given make whose Exif IFD has a Canon maker note, with the given camera settings
+// and shot info
+fn file(big_endian: bool, make: &str, camera_settings: &[u16], shot_info: &[u16]) -> Vec<u8> {
+    let mut file: File = File::new(big_endian);
+    let (camera_settings, shot_info): (Vec<u8>, Vec<u8>) =
+        (file.shorts(camera_settings), file.shorts(shot_info));
+    let maker_note: u32 = file.ifd(&[
+        (
+            0x0001,
+            SHORT,
+            size(&camera_settings) / 2,
+            Value::Bytes(camera_settings),
+        ),
+        (0x0004, SHORT, size(&shot_info) / 2, Value::Bytes(shot_info)),
+        (0x0006, ASCII, 6, Value::Bytes(b"Canon\0".to_vec())),
+    ]);
+    let maker_note_size: u32 = size(&file.bytes) - maker_note;
+    let exif: u32 = file.ifd(&[(
+        MAKER_NOTE,
+        UNDEFINED,
+        maker_note_size,
+        Value::At(maker_note),
+    )]);
+    let make: Vec<u8> = [make.as_bytes(), b"\0"].concat();
+    file.ifd0(&[
+        (MAKE, ASCII, size(&make), Value::Bytes(make)),
+        (EXIF_IFD, LONG, 1, Value::At(exif)),
+    ])
+}
+
+fn maker_note(bytes: Vec<u8>, options: ReaderOptions) -> Result<Option<CanonMakerNote>, TiffError> {
+    let mut tiff_reader: TiffReader<Cursor<Vec<u8>>> =
+        TiffReader::with_options(Cursor::new(bytes), options).unwrap();
+    let ifd0 = tiff_reader.ifds().unwrap().next().unwrap().unwrap();
+    tiff_reader.canon_maker_note(&ifd0)
+}
+
+// Camera settings of a recent body with an RF lens, Canon numbering them from 1 after the size
+fn camera_settings() -> Vec<u16> {
+    let mut values: Vec<u16> = vec![0; 49];
+    values[0] = 98;
+    values[1] = 2;
+    values[3] = 4;
+    // Not set
+    values[7] = 0xFFFF;
+    values[17] = 3;
+    values[19] = 0x3001;
+    values[20] = 3;
+    values[22] = 61182;
+    values[23] = 70;
+    values[24] = 24;
+    values[25] = 1;
+    values[34] = 1;
+    values
+}
+
+fn shot_info() -> Vec<u16> {
+    let mut values: Vec<u16> = vec![0; 34];
+    values[0] = 68;
+    values[2] = 160;
+    // -1 EV
+    values[6] = 0xFFE0;
+    values[7] = 1;
+    values[9] = 3;
+    values[12] = 153;
+    values[14] = 4;
+    values[19] = 0xFFFF;
+    values[20] = 150;
+    values[21] = 96;
+    values[22] = 224;
+    values
+}
+
+#[test]
+fn settings_are_decoded_in_either_byte_order() {
+    for big_endian in [false, true] {
+        let bytes: Vec<u8> = file(big_endian, "Canon", &camera_settings(), &shot_info());
+        let maker_note: CanonMakerNote = maker_note(bytes, ReaderOptions::new()).unwrap().unwrap();
+        assert_eq!(
+            maker_note.camera_settings,
+            Some(CameraSettings {
+                macro_mode: Some(2),
+                quality: Some(4),
+                flash_mode: Some(0),
+                continuous_drive: Some(0),
+                focus_mode: None,
+                metering_mode: Some(3),
+                af_point: Some(0x3001),
+                exposure_mode: Some(3),
+                lens_type: Some(61182),
+                max_focal_length: Some(70),
+                min_focal_length: Some(24),
+                focal_units: Some(1),
+                image_stabilization: Some(1),
+            })
+        );
+        assert_eq!(
+            maker_note.shot_info,
+            Some(ShotInfo {
+                auto_iso: Some(0),
+                base_iso: Some(160),
+                measured_ev: Some(0),
+                target_aperture: Some(0),
+                target_exposure_time: Some(0),
+                exposure_compensation: Some(-32),
+                white_balance: Some(1),
+                sequence_number: Some(3),
+                camera_temperature: Some(153),
+                af_points_in_focus: Some(4),
+                focus_distance_upper: None,
+                focus_distance_lower: Some(150),
+                f_number: Some(96),
+                exposure_time: Some(224),
+            })
+        );
+        // Other tags stay in the IFD
+        assert_eq!(maker_note.ifd.fields.len(), 3);
+    }
+}
+
+#[test]
+fn settings_past_the_end_of_short_arrays_are_none() {
+    let bytes: Vec<u8> = file(false, "Canon", &camera_settings()[..20], &shot_info()[..8]);
+    let maker_note: CanonMakerNote = maker_note(bytes, ReaderOptions::new()).unwrap().unwrap();
+    let camera_settings: CameraSettings = maker_note.camera_settings.unwrap();
+    assert_eq!(camera_settings.metering_mode, Some(3));
+    assert_eq!(camera_settings.af_point, Some(0x3001));
+    assert_eq!(camera_settings.exposure_mode, None);
+    assert_eq!(camera_settings.lens_type, None);
+    let shot_info: ShotInfo = maker_note.shot_info.unwrap();
+    assert_eq!(shot_info.white_balance, Some(1));
+    assert_eq!(shot_info.sequence_number, None);
+}
+
+#[test]
+fn maker_notes_are_found_when_reading_lazily() {
+    let bytes: Vec<u8> = file(false, "Canon", &camera_settings(), &shot_info());
+    let maker_note: CanonMakerNote = maker_note(bytes, ReaderOptions::new().lazy(true))
+        .unwrap()
+        .unwrap();
+    assert_eq!(maker_note.camera_settings.unwrap().lens_type, Some(61182));
+}
+
+#[test]
+fn other_makes_have_no_canon_maker_note() {
+    let bytes: Vec<u8> = file(false, "NIKON CORPORATION", &camera_settings(), &shot_info());
+    assert!(maker_note(bytes, ReaderOptions::new()).unwrap().is_none());
+}
+
+#[test]
+fn maker_notes_out_of_the_file_are_rejected() {
+    let mut bytes: Vec<u8> = file(false, "Canon", &camera_settings(), &shot_info());
+    // Count of the first maker note entry, the camera settings, past the end of the file
+    bytes[8 + 2 + 4..8 + 2 + 8].copy_from_slice(&0x1000_u32.to_le_bytes());
+    assert!(maker_note(bytes, ReaderOptions::new()).is_err());
+}
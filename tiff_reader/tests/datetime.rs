@@ -0,0 +1,159 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use tiff_reader::{DateTime, LenientDateTime};
+
+fn date_time(date: [u16; 3], time: [u8; 3]) -> DateTime {
+    DateTime {
+        year: date[0],
+        month: u8::try_from(date[1]).unwrap(),
+        day: u8::try_from(date[2]).unwrap(),
+        hour: time[0],
+        minute: time[1],
+        second: time[2],
+        nanosecond: 0,
+        offset: None,
+    }
+}
+
+fn lenient(text: &str) -> Option<(DateTime, bool)> {
+    DateTime::parse_lenient(text).map(|LenientDateTime { date_time, valid }| (date_time, valid))
+}
+
+#[test]
+fn exif_dates_parse() {
+    assert_eq!(
+        DateTime::parse("2023:05:17 10:11:12").unwrap(),
+        date_time([2023, 5, 17], [10, 11, 12])
+    );
+}
+
+#[test]
+fn trailing_nuls_are_ignored() {
+    assert_eq!(
+        DateTime::parse("2023:05:17 10:11:12\0\0").unwrap(),
+        date_time([2023, 5, 17], [10, 11, 12])
+    );
+}
+
+#[test]
+fn iso_8601_fractions_and_offsets_parse() {
+    let mut expected: DateTime = date_time([2023, 5, 17], [10, 11, 12]);
+    expected.nanosecond = 250_000_000;
+    assert_eq!(DateTime::parse("2023-05-17T10:11:12.25").unwrap(), expected);
+
+    expected.offset = Some(0);
+    assert_eq!(
+        DateTime::parse("2023-05-17T10:11:12.25Z").unwrap(),
+        expected
+    );
+
+    expected.nanosecond = 0;
+    expected.offset = Some(5 * 60 + 30);
+    assert_eq!(
+        DateTime::parse("2023-05-17T10:11:12+05:30").unwrap(),
+        expected
+    );
+
+    expected.offset = Some(-3 * 60);
+    assert_eq!(
+        DateTime::parse("2023-05-17T10:11:12-03:00").unwrap(),
+        expected
+    );
+    assert_eq!(expected.to_string(), "2023-05-17T10:11:12-03:00");
+}
+
+#[test]
+fn out_of_range_fields_are_rejected() {
+    for text in [
+        "2023:13:17 10:11:12",
+        "2023:00:17 10:11:12",
+        "2023:05:32 10:11:12",
+        "2023:05:17 24:11:12",
+        "2023:05:17 10:60:12",
+        "2023:05:17 10:11:61",
+        "2023-05-17T10:11:12+15:00",
+        "2023-05-17T10:11:12+05:60",
+        "2023:05:17 10:11:12 ",
+        "0000:00:00 00:00:00",
+    ] {
+        assert!(DateTime::parse(text).is_err(), "{text}");
+    }
+    for text in [
+        "2023/13/17 10:11:12",
+        "2023/05/32",
+        "2023/05/17 24:00",
+        "2023/05/17 10:60",
+        "2023/05/17 10:11:61",
+    ] {
+        assert_eq!(lenient(text), None, "{text}");
+    }
+}
+
+#[test]
+fn usual_mistakes_are_taken_leniently() {
+    let expected: DateTime = date_time([2023, 5, 17], [10, 11, 0]);
+    assert_eq!(lenient("2023:05:17 10:11"), Some((expected, false)));
+    assert_eq!(lenient("2023/05/17 10:11:00"), Some((expected, false)));
+    assert_eq!(lenient("2023-05-17 10:11"), Some((expected, false)));
+    assert_eq!(lenient("2023.5.17 10:11"), Some((expected, false)));
+    assert_eq!(
+        lenient("2023/5/7"),
+        Some((date_time([2023, 5, 7], [0, 0, 0]), false))
+    );
+    assert_eq!(
+        lenient("2023:05:17 9:05:07\0"),
+        Some((date_time([2023, 5, 17], [9, 5, 7]), false))
+    );
+
+    // Zero dates, which Exif allows for unknown dates
+    assert_eq!(
+        lenient("0000:00:00 00:00:00"),
+        Some((date_time([0, 0, 0], [0, 0, 0]), false))
+    );
+
+    // Anything parse() takes is valid
+    assert_eq!(lenient("2023:05:17 10:11:00"), Some((expected, true)));
+    assert_eq!(lenient("May 17, 2023"), None);
+}
+
+#[test]
+fn leap_seconds_parse() {
+    let leap_second: DateTime = DateTime::parse("2016:12:31 23:59:60").unwrap();
+    assert_eq!(leap_second, date_time([2016, 12, 31], [23, 59, 60]));
+    assert_eq!(leap_second.to_string(), "2016-12-31T23:59:60");
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn leap_seconds_convert_to_chrono() {
+    use chrono::{NaiveDate, NaiveDateTime, Timelike};
+
+    let leap_second: NaiveDateTime =
+        NaiveDateTime::try_from(DateTime::parse("2016:12:31 23:59:60").unwrap()).unwrap();
+    assert_eq!(
+        leap_second,
+        NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_000_000_000)
+            .unwrap()
+    );
+    assert_eq!(leap_second.second(), 59);
+    assert_eq!(leap_second.nanosecond(), 1_000_000_000);
+
+    let date_time: chrono::DateTime<chrono::FixedOffset> = DateTime::parse("2016-12-31T23:59:60Z")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(date_time.naive_local(), leap_second);
+}
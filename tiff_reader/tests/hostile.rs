@@ -0,0 +1,278 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::io::Cursor;
+
+use tiff_reader::{ReaderOptions, TiffError, TiffReader};
+
+#[cfg(any(feature = "fuzzing", feature = "tokio"))]
+const BYTE: u16 = 1;
+#[cfg(any(feature = "fuzzing", feature = "tokio"))]
+const ASCII: u16 = 2;
+#[cfg(any(feature = "fuzzing", feature = "tokio"))]
+const SHORT: u16 = 3;
+const LONG: u16 = 4;
+const IMAGE_WIDTH: u16 = 256;
+const SUB_IFDS: u16 = 330;
+
+// Little-endian header pointing at an IFD right after it, at offset 8
+fn header() -> Vec<u8> {
+    let mut bytes: Vec<u8> = b"II".to_vec();
+    bytes.extend(42u16.to_le_bytes());
+    bytes.extend(8u32.to_le_bytes());
+    bytes
+}
+
+// IFD with the given entries, each a tag, type, count and value or offset, and no next IFD
+fn ifd(entries: &[(u16, u16, u32, u32)]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = u16::try_from(entries.len()).unwrap().to_le_bytes().to_vec();
+    for (tag, type_, count, value) in entries {
+        bytes.extend(tag.to_le_bytes());
+        bytes.extend(type_.to_le_bytes());
+        bytes.extend(count.to_le_bytes());
+        bytes.extend(value.to_le_bytes());
+    }
+    bytes.extend(0u32.to_le_bytes());
+    bytes
+}
+
+fn offset(bytes: &[u8]) -> u32 {
+    u32::try_from(bytes.len()).unwrap()
+}
+
+#[test]
+fn deeply_nested_sub_ifds_fail_instead_of_overflowing_the_stack() {
+    // Each IFD points to the next through SubIFDs, 18 bytes per level
+    let mut bytes: Vec<u8> = header();
+    for _ in 0..100_000 {
+        let next: u32 = offset(&bytes) + 18;
+        bytes.extend(ifd(&[(SUB_IFDS, LONG, 1, next)]));
+    }
+    bytes.extend(ifd(&[(IMAGE_WIDTH, LONG, 1, 1)]));
+
+    let result: Result<_, TiffError> = TiffReader::new(Cursor::new(bytes)).unwrap().read_ifd_at(8);
+    assert!(matches!(
+        result,
+        Err(TiffError::LimitExceeded {
+            limit: "IFD depth",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn sub_ifds_pointing_at_the_same_ifd_are_read_once() {
+    // Each IFD points four times to the next, which would be read 4³⁰ times otherwise
+    const LEVELS: usize = 30;
+    let mut bytes: Vec<u8> = header();
+    for _ in 0..LEVELS {
+        let offsets: u32 = offset(&bytes) + 18;
+        let next: u32 = offsets + 16;
+        bytes.extend(ifd(&[(SUB_IFDS, LONG, 4, offsets)]));
+        for _ in 0..4 {
+            bytes.extend(next.to_le_bytes());
+        }
+    }
+    bytes.extend(ifd(&[(IMAGE_WIDTH, LONG, 1, 1)]));
+
+    let result: Result<_, TiffError> = TiffReader::new(Cursor::new(bytes.clone()))
+        .unwrap()
+        .read_ifd_at(8);
+    assert!(matches!(result, Err(TiffError::BadOffset { .. })));
+
+    let mut tiff_reader: TiffReader<Cursor<Vec<u8>>> =
+        TiffReader::with_options(Cursor::new(bytes), ReaderOptions::new().strict(false)).unwrap();
+    let mut ifd: tiff_reader::Ifd = tiff_reader.read_ifd_at(8).unwrap();
+    let mut depth: usize = 0;
+    while let Some(child) = ifd.sub_ifds.pop() {
+        assert!(ifd.sub_ifds.is_empty());
+        ifd = child;
+        depth += 1;
+    }
+    assert_eq!(depth, LEVELS);
+    assert_eq!(tiff_reader.warnings().len(), 3 * LEVELS);
+}
+
+// Smallest DNG worth breaking: a 1×1 thumbnail in IFD0 and a 4×2 16-bit RGGB raw image in a SubIFD,
+// both uncompressed in a single strip
+#[cfg(any(feature = "fuzzing", feature = "tokio"))]
+fn dng() -> Vec<u8> {
+    let mut bytes: Vec<u8> = header();
+    for sample in 1u16..=8 {
+        bytes.extend((sample * 100).to_le_bytes());
+    }
+    // IFD0 goes after the pixels
+    bytes.splice(4..8, offset(&bytes).to_le_bytes());
+    let raw_ifd: u32 = offset(&bytes) + 2 + 12 * 12 + 4;
+    bytes.extend(ifd(&[
+        (254, LONG, 1, 1),
+        (256, LONG, 1, 1),
+        (257, LONG, 1, 1),
+        (258, SHORT, 1, 8),
+        (259, SHORT, 1, 1),
+        (262, SHORT, 1, 1),
+        (273, LONG, 1, 8),
+        (277, SHORT, 1, 1),
+        (279, LONG, 1, 1),
+        (SUB_IFDS, LONG, 1, raw_ifd),
+        (50706, BYTE, 4, u32::from_le_bytes([1, 4, 0, 0])),
+        (50708, ASCII, 4, u32::from_le_bytes(*b"Cam\0")),
+    ]));
+    bytes.extend(ifd(&[
+        (254, LONG, 1, 0),
+        (256, LONG, 1, 4),
+        (257, LONG, 1, 2),
+        (258, SHORT, 1, 16),
+        (259, SHORT, 1, 1),
+        (262, SHORT, 1, 32803),
+        (273, LONG, 1, 8),
+        (277, SHORT, 1, 1),
+        (278, LONG, 1, 2),
+        (279, LONG, 1, 16),
+        (33421, SHORT, 2, 2 | 2 << 16),
+        (33422, BYTE, 4, u32::from_le_bytes([0, 1, 1, 2])),
+    ]));
+    bytes
+}
+
+// Everything there is to do with a file, none of which may panic, whatever the bytes
+#[cfg(feature = "fuzzing")]
+fn parse(bytes: &[u8]) {
+    let Ok(dng) = tiff_reader::parse_arbitrary_bytes(bytes) else {
+        return;
+    };
+    let _ = dng.validate();
+    let _ = dng.storage_report(u64::try_from(bytes.len()).unwrap());
+    let _ = dng.black_levels();
+    let _ = dng.camera();
+    let options: ReaderOptions = ReaderOptions::new().max_total_bytes(bytes.len() * 16);
+    let mut tiff_reader: TiffReader<Cursor<&[u8]>> =
+        TiffReader::with_options(Cursor::new(bytes), options).unwrap();
+    let _ = tiff_reader.read_raw_image(&dng.hires_ifd);
+    let _ = tiff_reader.previews(&dng);
+}
+
+#[cfg(feature = "fuzzing")]
+#[test]
+fn the_sample_dng_reads() {
+    let bytes: Vec<u8> = dng();
+    let dng: tiff_reader::Dng = tiff_reader::parse_arbitrary_bytes(&bytes).unwrap();
+    assert!(dng.warnings.is_empty());
+    let raw_image: tiff_reader::RawImage = TiffReader::new(Cursor::new(&bytes))
+        .unwrap()
+        .read_raw_image(&dng.hires_ifd)
+        .unwrap();
+    assert_eq!(raw_image.data, [100, 200, 300, 400, 500, 600, 700, 800]);
+}
+
+#[cfg(feature = "fuzzing")]
+#[test]
+fn truncated_files_do_not_panic() {
+    let bytes: Vec<u8> = dng();
+    for length in 0..bytes.len() {
+        parse(&bytes[..length]);
+    }
+}
+
+#[cfg(feature = "fuzzing")]
+#[test]
+fn mutated_files_do_not_panic() {
+    let bytes: Vec<u8> = dng();
+    for index in 0..bytes.len() {
+        for value in [
+            0x00,
+            0x01,
+            0x7f,
+            0x80,
+            0xff,
+            bytes[index] ^ 0x01,
+            bytes[index] ^ 0x80,
+        ] {
+            let mut mutated: Vec<u8> = bytes.clone();
+            mutated[index] = value;
+            parse(&mutated);
+        }
+    }
+}
+
+// Source that counts the bytes read from it
+#[cfg(feature = "tokio")]
+struct Counted {
+    cursor: Cursor<Vec<u8>>,
+    bytes_read: usize,
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncRead for Counted {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        context: &mut std::task::Context<'_>,
+        buffer: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        let before: usize = buffer.filled().len();
+        let poll = std::pin::Pin::new(&mut self.cursor).poll_read(context, buffer);
+        self.bytes_read += buffer.filled().len() - before;
+        poll
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncSeek for Counted {
+    fn start_seek(
+        mut self: std::pin::Pin<&mut Self>,
+        position: std::io::SeekFrom,
+    ) -> std::io::Result<()> {
+        std::pin::Pin::new(&mut self.cursor).start_seek(position)
+    }
+
+    fn poll_complete(
+        mut self: std::pin::Pin<&mut Self>,
+        context: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<u64>> {
+        std::pin::Pin::new(&mut self.cursor).poll_complete(context)
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn async_reads_fetch_only_what_is_parsed() {
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    // Far more data than the metadata, as in a raw file
+    let mut bytes: Vec<u8> = dng();
+    let expected: tiff_reader::Dng = TiffReader::new(Cursor::new(&bytes))
+        .unwrap()
+        .read_dng()
+        .unwrap();
+    bytes.resize(16 << 20, 0);
+    let mut source: Counted = Counted {
+        cursor: Cursor::new(bytes),
+        bytes_read: 0,
+    };
+
+    // Cursors never wait, so polling once is enough and no runtime is needed
+    let dng: tiff_reader::Dng = {
+        let future = std::pin::pin!(tiff_reader::read_dng_async(
+            &mut source,
+            ReaderOptions::default()
+        ));
+        let Poll::Ready(result) = future.poll(&mut Context::from_waker(Waker::noop())) else {
+            panic!("Reading from a cursor should not wait");
+        };
+        result.unwrap()
+    };
+    assert_eq!(dng.hires_ifd.position, expected.hires_ifd.position);
+    assert_eq!(dng.ifd0.fields.len(), expected.ifd0.fields.len());
+    assert!(source.bytes_read <= 64 * 1024);
+}
@@ -0,0 +1,165 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use tiff_reader::{IccProfile, TiffError, Vec3};
+
+// Profile with the given tags, whose data follows the tag table in the order given
+fn profile(tags: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let mut header: Vec<u8> = vec![0; 128];
+    header[4..8].copy_from_slice(b"lcms");
+    header[8..10].copy_from_slice(&[4, 0x30]);
+    header[12..16].copy_from_slice(b"mntr");
+    header[16..20].copy_from_slice(b"RGB ");
+    header[20..24].copy_from_slice(b"XYZ ");
+    header[36..40].copy_from_slice(b"acsp");
+    header[68..80].copy_from_slice(&xyz([0xF6D6, 0x1_0000, 0xD32D])[8..]);
+
+    let mut table: Vec<u8> = u32_bytes(tags.len());
+    let mut data: Vec<u8> = Vec::new();
+    let start: usize = 128 + 4 + 12 * tags.len();
+    for (signature, contents) in tags {
+        table.extend_from_slice(*signature);
+        table.extend_from_slice(&u32_bytes(start + data.len()));
+        table.extend_from_slice(&u32_bytes(contents.len()));
+        data.extend_from_slice(contents);
+    }
+
+    let mut profile: Vec<u8> = [header, table, data].concat();
+    let size: Vec<u8> = u32_bytes(profile.len());
+    profile[0..4].copy_from_slice(&size);
+    profile
+}
+
+fn u32_bytes(value: usize) -> Vec<u8> {
+    u32::try_from(value).unwrap().to_be_bytes().to_vec()
+}
+
+// XYZType holding a single XYZNumber, in 1/65536ths as s15Fixed16Number
+fn xyz(values: [i32; 3]) -> Vec<u8> {
+    let mut xyz: Vec<u8> = b"XYZ \0\0\0\0".to_vec();
+    for value in values {
+        xyz.extend_from_slice(&value.to_be_bytes());
+    }
+    xyz
+}
+
+// textDescriptionType, with only its ASCII description
+fn desc(text: &str) -> Vec<u8> {
+    let mut desc: Vec<u8> = b"desc\0\0\0\0".to_vec();
+    desc.extend_from_slice(&u32_bytes(text.len() + 1));
+    desc.extend_from_slice(text.as_bytes());
+    desc.push(0);
+    desc
+}
+
+// multiLocalizedUnicodeType, with a record per language and country
+fn mluc(records: &[(&[u8; 4], &str)]) -> Vec<u8> {
+    let mut mluc: Vec<u8> = b"mluc\0\0\0\0".to_vec();
+    mluc.extend_from_slice(&u32_bytes(records.len()));
+    mluc.extend_from_slice(&u32_bytes(12));
+    let mut strings: Vec<u8> = Vec::new();
+    let start: usize = 16 + 12 * records.len();
+    for (language_country, text) in records {
+        let utf16: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        mluc.extend_from_slice(*language_country);
+        mluc.extend_from_slice(&u32_bytes(utf16.len()));
+        mluc.extend_from_slice(&u32_bytes(start + strings.len()));
+        strings.extend_from_slice(&utf16);
+    }
+    mluc.extend_from_slice(&strings);
+    mluc
+}
+
+fn description(tag: Vec<u8>) -> Option<String> {
+    IccProfile::parse(&profile(&[(b"desc", tag)]))
+        .unwrap()
+        .description
+}
+
+#[test]
+fn header_is_decoded() {
+    let bytes: Vec<u8> = profile(&[(b"wtpt", xyz([0xF354, 0x1_0000, 0x1_16C9]))]);
+    let icc: IccProfile = IccProfile::parse(&bytes).unwrap();
+    assert_eq!(icc.header.size, u32::try_from(bytes.len()).unwrap());
+    assert_eq!(icc.header.cmm, "lcms");
+    assert_eq!(icc.header.version, (4, 3, 0));
+    assert_eq!(icc.header.device_class, "mntr");
+    assert_eq!(icc.header.color_space, "RGB");
+    assert_eq!(icc.header.connection_space, "XYZ");
+    assert!((icc.header.illuminant.0[2] - 0.8249).abs() < 1e-4);
+    let Some(Vec3(white_point)) = icc.white_point else {
+        panic!("no white point");
+    };
+    assert!((white_point[0] - 0.9505).abs() < 1e-4);
+    assert_eq!(icc.description, None);
+    assert_eq!(icc.bytes(), bytes);
+}
+
+#[test]
+fn descriptions_are_read_from_desc_and_mluc() {
+    assert_eq!(description(desc("sRGB")), Some("sRGB".to_string()));
+    assert_eq!(
+        description(mluc(&[(b"deDE", "Anzeige"), (b"enUS", "Display")])),
+        Some("Display".to_string())
+    );
+
+    // With no English record, the first one
+    assert_eq!(
+        description(mluc(&[(b"frFR", "Écran"), (b"deDE", "Anzeige")])),
+        Some("Écran".to_string())
+    );
+    assert_eq!(description(mluc(&[])), None);
+
+    // Any other type has no description to give
+    assert_eq!(description(b"text\0\0\0\0sRGB\0".to_vec()), None);
+}
+
+#[test]
+fn out_of_range_tags_are_rejected() {
+    // Tag data past the end of the profile
+    let mut bytes: Vec<u8> = profile(&[(b"desc", desc("sRGB"))]);
+    bytes[128 + 8..128 + 12].copy_from_slice(&u32_bytes(0x1000));
+    assert!(matches!(
+        IccProfile::parse(&bytes),
+        Err(TiffError::TruncatedValue { .. })
+    ));
+
+    // Size running past the end, even if only by one byte
+    let mut bytes: Vec<u8> = profile(&[(b"desc", desc("sRGB"))]);
+    let size: usize = desc("sRGB").len() + 1;
+    bytes[128 + 12..128 + 16].copy_from_slice(&u32_bytes(size));
+    assert!(matches!(
+        IccProfile::parse(&bytes),
+        Err(TiffError::TruncatedValue { .. })
+    ));
+
+    // Offsets and sizes inside tag data, too
+    let mut tag: Vec<u8> = mluc(&[(b"enUS", "Display")]);
+    tag[16 + 8..16 + 12].copy_from_slice(&u32_bytes(0xFFFF_FFFF));
+    assert!(IccProfile::parse(&profile(&[(b"desc", tag)])).is_err());
+    let mut tag: Vec<u8> = desc("sRGB");
+    tag[8..12].copy_from_slice(&u32_bytes(100));
+    assert!(IccProfile::parse(&profile(&[(b"desc", tag)])).is_err());
+
+    // More tags than the profile has room for
+    let mut bytes: Vec<u8> = profile(&[]);
+    bytes[128..132].copy_from_slice(&u32_bytes(1000));
+    assert!(matches!(
+        IccProfile::parse(&bytes),
+        Err(TiffError::InvalidData(_))
+    ));
+
+    // Not a profile at all
+    assert!(IccProfile::parse(&[0; 127]).is_err());
+    assert!(IccProfile::parse(&[0; 132]).is_err());
+}
@@ -0,0 +1,342 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use std::io::Cursor;
+
+use data::Tag;
+use tiff_reader::{Ifd, RawImage, Segment, TiffError, TiffReader};
+
+const SHORT: u16 = 3;
+const LONG: u16 = 4;
+const IMAGE_WIDTH: u16 = 256;
+const IMAGE_LENGTH: u16 = 257;
+const BITS_PER_SAMPLE: u16 = 258;
+const FILL_ORDER: u16 = 266;
+const STRIP_OFFSETS: u16 = 273;
+const SAMPLES_PER_PIXEL: u16 = 277;
+const ROWS_PER_STRIP: u16 = 278;
+const STRIP_BYTE_COUNTS: u16 = 279;
+const PLANAR_CONFIGURATION: u16 = 284;
+const TILE_WIDTH: u16 = 322;
+const TILE_LENGTH: u16 = 323;
+const TILE_OFFSETS: u16 = 324;
+const TILE_BYTE_COUNTS: u16 = 325;
+
+// Padding, not part of the image
+const PAD: u8 = 0xEE;
+
+// Where data starts, right after the header
+const DATA: u32 = 8;
+
+// TIFF with data right after the header and, after it, an IFD with the given entries, each a tag,
+// type, SHORT or LONG, and values, sorted by tag. Values that do not fit in an entry go after the
+// IFD.
+fn tiff(big_endian: bool, entries: &[(u16, u16, Vec<u32>)], data: &[u8]) -> Vec<u8> {
+    let u16_bytes = |value: u16| -> [u8; 2] {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+    let u32_bytes = |value: u32| -> [u8; 4] {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+    let values_bytes = |type_: u16, values: &[u32]| -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| match type_ {
+                SHORT => u16_bytes(u16::try_from(*value).unwrap()).to_vec(),
+                _ => u32_bytes(*value).to_vec(),
+            })
+            .collect()
+    };
+
+    let mut bytes: Vec<u8> = if big_endian {
+        b"MM".to_vec()
+    } else {
+        b"II".to_vec()
+    };
+    bytes.extend(u16_bytes(42));
+    // IFDs start on a word boundary
+    let ifd_offset: u32 = (DATA + u32::try_from(data.len()).unwrap()).next_multiple_of(2);
+    bytes.extend(u32_bytes(ifd_offset));
+    bytes.extend(data);
+    bytes.resize(usize::try_from(ifd_offset).unwrap(), 0);
+
+    let mut values: Vec<u8> = Vec::new();
+    let values_offset: u32 = ifd_offset + 2 + 12 * u32::try_from(entries.len()).unwrap() + 4;
+    bytes.extend(u16_bytes(u16::try_from(entries.len()).unwrap()));
+    for (tag, type_, entry_values) in entries {
+        bytes.extend(u16_bytes(*tag));
+        bytes.extend(u16_bytes(*type_));
+        bytes.extend(u32_bytes(u32::try_from(entry_values.len()).unwrap()));
+        let mut value: Vec<u8> = values_bytes(*type_, entry_values);
+        if value.len() > 4 {
+            let offset: u32 = values_offset + u32::try_from(values.len()).unwrap();
+            values.append(&mut value);
+            value = u32_bytes(offset).to_vec();
+        }
+        value.resize(4, 0);
+        bytes.extend(value);
+    }
+    bytes.extend(u32_bytes(0));
+    bytes.extend(values);
+    bytes
+}
+
+// Reader of the TIFF tiff() makes, in any order of entries, and its IFD
+fn open(
+    big_endian: bool,
+    mut entries: Vec<(u16, u16, Vec<u32>)>,
+    data: &[u8],
+) -> (TiffReader<Cursor<Vec<u8>>>, Ifd) {
+    entries.sort_by_key(|(tag, _, _)| *tag);
+    let mut tiff_reader: TiffReader<Cursor<Vec<u8>>> =
+        TiffReader::new(Cursor::new(tiff(big_endian, &entries, data))).unwrap();
+    let ifd: Ifd = tiff_reader.ifds().unwrap().next().unwrap().unwrap();
+    (tiff_reader, ifd)
+}
+
+// Single strip image of width by length samples of bits each, stored as data
+fn raw_image(big_endian: bool, bits: u32, width: u32, length: u32, data: &[u8]) -> RawImage {
+    raw_image_with(big_endian, bits, width, length, data, &[])
+}
+
+fn raw_image_with(
+    big_endian: bool,
+    bits: u32,
+    width: u32,
+    length: u32,
+    data: &[u8],
+    more: &[(u16, u16, Vec<u32>)],
+) -> RawImage {
+    let byte_count: u32 = u32::try_from(data.len()).unwrap();
+    let mut entries: Vec<(u16, u16, Vec<u32>)> = vec![
+        (IMAGE_WIDTH, LONG, vec![width]),
+        (IMAGE_LENGTH, LONG, vec![length]),
+        (BITS_PER_SAMPLE, SHORT, vec![bits]),
+        (STRIP_OFFSETS, LONG, vec![DATA]),
+        (ROWS_PER_STRIP, LONG, vec![length]),
+        (STRIP_BYTE_COUNTS, LONG, vec![byte_count]),
+    ];
+    entries.extend(more.iter().cloned());
+    let (mut tiff_reader, ifd): (TiffReader<Cursor<Vec<u8>>>, Ifd) =
+        open(big_endian, entries, data);
+    tiff_reader.read_raw_image(&ifd).unwrap()
+}
+
+#[test]
+fn packed_samples_are_unpacked_most_significant_bit_first() {
+    // 1111111111 0000000000 0101010101 1010101010
+    let image: RawImage = raw_image(false, 10, 4, 1, &[0xFF, 0xC0, 0x05, 0x56, 0xAA]);
+    assert_eq!(image.data, [0x3FF, 0x000, 0x155, 0x2AA]);
+
+    // ABC 123
+    let image: RawImage = raw_image(false, 12, 2, 1, &[0xAB, 0xC1, 0x23]);
+    assert_eq!(image.data, [0xABC, 0x123]);
+
+    // 11111111111111 00000000000001 0000, padded to a whole byte
+    let image: RawImage = raw_image(false, 14, 2, 1, &[0xFF, 0xFC, 0x00, 0x10]);
+    assert_eq!(image.data, [0x3FFF, 0x0001]);
+}
+
+#[test]
+fn packing_ignores_byte_order() {
+    let image: RawImage = raw_image(true, 12, 2, 1, &[0xAB, 0xC1, 0x23]);
+    assert_eq!(image.data, [0xABC, 0x123]);
+}
+
+#[test]
+fn each_row_starts_on_a_byte_boundary() {
+    // Three 12-bit samples take 36 bits, so each row ends with 4 bits of padding
+    let image: RawImage = raw_image(
+        false,
+        12,
+        3,
+        2,
+        &[
+            0x12, 0x34, 0x56, 0x78, 0x90, //
+            0xAB, 0xCD, 0xEF, 0x01, 0x20,
+        ],
+    );
+    assert_eq!(image.data, [0x123, 0x456, 0x789, 0xABC, 0xDEF, 0x012]);
+    assert_eq!(image.sample(1, 2, 0), Some(0x012));
+}
+
+#[test]
+fn fill_order_2_reverses_the_bits_of_each_byte() {
+    // 0xAB, 0xC1 and 0x23, each with its bits reversed
+    let image: RawImage = raw_image_with(
+        false,
+        12,
+        2,
+        1,
+        &[0xD5, 0x83, 0xC4],
+        &[(FILL_ORDER, SHORT, vec![2])],
+    );
+    assert_eq!(image.data, [0xABC, 0x123]);
+}
+
+#[test]
+fn sixteen_bit_samples_follow_the_byte_order() {
+    let image: RawImage = raw_image(false, 16, 2, 1, &[0x34, 0x12, 0xCD, 0xAB]);
+    assert_eq!(image.data, [0x1234, 0xABCD]);
+
+    let image: RawImage = raw_image(true, 16, 2, 1, &[0x12, 0x34, 0xAB, 0xCD]);
+    assert_eq!(image.data, [0x1234, 0xABCD]);
+}
+
+#[test]
+fn samples_of_a_pixel_stay_together() {
+    let image: RawImage = raw_image_with(
+        false,
+        8,
+        2,
+        1,
+        &[1, 2, 3, 4, 5, 6],
+        &[(SAMPLES_PER_PIXEL, SHORT, vec![3])],
+    );
+    assert_eq!(image.data, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(image.sample(0, 1, 0), Some(4));
+}
+
+// 8-bit image 2 pixels wide and 3 long, in strips of 2 rows
+fn strips(data: &[u8], byte_counts: Vec<u32>) -> (TiffReader<Cursor<Vec<u8>>>, Ifd) {
+    open(
+        false,
+        vec![
+            (IMAGE_WIDTH, LONG, vec![2]),
+            (IMAGE_LENGTH, LONG, vec![3]),
+            (BITS_PER_SAMPLE, SHORT, vec![8]),
+            (STRIP_OFFSETS, LONG, vec![DATA, DATA + byte_counts[0]]),
+            (ROWS_PER_STRIP, LONG, vec![2]),
+            (STRIP_BYTE_COUNTS, LONG, byte_counts),
+        ],
+        data,
+    )
+}
+
+#[test]
+fn last_strip_holds_only_the_rows_left() {
+    // Stored with and without the padding rows a full strip would have
+    for data in [&[1, 2, 3, 4, 5, 6][..], &[1, 2, 3, 4, 5, 6, PAD, PAD]] {
+        let last: u32 = u32::try_from(data.len()).unwrap() - 4;
+        let (mut tiff_reader, ifd): (TiffReader<Cursor<Vec<u8>>>, Ifd) =
+            strips(data, vec![4, last]);
+
+        let segments: Vec<Segment> = ifd.segments().unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!((segments[1].row, segments[1].stored_length), (2, 2));
+        assert_eq!((segments[1].width, segments[1].length), (2, 1));
+        assert_eq!(
+            tiff_reader.read_uncompressed_image(&ifd).unwrap(),
+            [1, 2, 3, 4, 5, 6]
+        );
+    }
+}
+
+#[test]
+fn padding_of_edge_tiles_is_dropped() {
+    // 3 by 3 image, pixels numbered 1 to 9, in 2 by 2 tiles
+    let tiles: [[u8; 4]; 4] = [
+        [1, 2, 4, 5],
+        [3, PAD, 6, PAD],
+        [7, 8, PAD, PAD],
+        [9, PAD, PAD, PAD],
+    ];
+    let (mut tiff_reader, ifd): (TiffReader<Cursor<Vec<u8>>>, Ifd) = open(
+        false,
+        vec![
+            (IMAGE_WIDTH, LONG, vec![3]),
+            (IMAGE_LENGTH, LONG, vec![3]),
+            (BITS_PER_SAMPLE, SHORT, vec![8]),
+            (TILE_WIDTH, LONG, vec![2]),
+            (TILE_LENGTH, LONG, vec![2]),
+            (
+                TILE_OFFSETS,
+                LONG,
+                vec![DATA, DATA + 4, DATA + 8, DATA + 12],
+            ),
+            (TILE_BYTE_COUNTS, LONG, vec![4; 4]),
+        ],
+        tiles.as_flattened(),
+    );
+
+    let segments: Vec<Segment> = ifd.segments().unwrap();
+    let placement: Vec<(u32, u32, u32, u32)> = segments
+        .iter()
+        .map(|segment| (segment.row, segment.column, segment.width, segment.length))
+        .collect();
+    assert_eq!(
+        placement,
+        [(0, 0, 2, 2), (0, 2, 1, 2), (2, 0, 2, 1), (2, 2, 1, 1)]
+    );
+    assert_eq!(
+        tiff_reader.read_uncompressed_image(&ifd).unwrap(),
+        [1, 2, 3, 4, 5, 6, 7, 8, 9]
+    );
+}
+
+#[test]
+fn planar_images_have_segments_for_every_sample() {
+    let planar = |offsets: Vec<u32>| -> Ifd {
+        open(
+            false,
+            vec![
+                (IMAGE_WIDTH, LONG, vec![2]),
+                (IMAGE_LENGTH, LONG, vec![1]),
+                (BITS_PER_SAMPLE, SHORT, vec![8, 8, 8]),
+                (STRIP_OFFSETS, LONG, offsets.clone()),
+                (SAMPLES_PER_PIXEL, SHORT, vec![3]),
+                (STRIP_BYTE_COUNTS, LONG, vec![2; offsets.len()]),
+                (PLANAR_CONFIGURATION, SHORT, vec![2]),
+            ],
+            &[1, 2, 3, 4, 5, 6],
+        )
+        .1
+    };
+
+    let segments: Vec<Segment> = planar(vec![DATA, DATA + 2, DATA + 4]).segments().unwrap();
+    let planes: Vec<(u32, u64)> = segments
+        .iter()
+        .map(|segment| (segment.plane, segment.offset))
+        .collect();
+    assert_eq!(planes, [(0, 8), (1, 10), (2, 12)]);
+
+    assert!(matches!(
+        planar(vec![DATA]).segments(),
+        Err(TiffError::UnexpectedCount {
+            expected: 3,
+            found: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn offsets_and_byte_counts_must_agree() {
+    let (_, ifd): (TiffReader<Cursor<Vec<u8>>>, Ifd) = strips(&[1, 2, 3, 4, 5, 6], vec![4]);
+    assert!(matches!(
+        ifd.segments(),
+        Err(TiffError::UnexpectedCount {
+            tag: Tag::StripByteCounts,
+            expected: 2,
+            found: 1,
+        })
+    ));
+}
@@ -0,0 +1,145 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use data::Tag;
+use tiff_reader::{photoshop_resources, Field, Fields, Ifd, Iptc, TiffError};
+
+fn dataset(record: u8, number: u8, data: &[u8]) -> Vec<u8> {
+    let mut dataset: Vec<u8> = vec![0x1C, record, number];
+    dataset.extend_from_slice(&u16::try_from(data.len()).unwrap().to_be_bytes());
+    dataset.extend_from_slice(data);
+    dataset
+}
+
+// Image resource, with its name and data padded to an even size
+fn resource(id: u16, name: &str, data: &[u8]) -> Vec<u8> {
+    let mut resource: Vec<u8> = b"8BIM".to_vec();
+    resource.extend_from_slice(&id.to_be_bytes());
+    resource.push(u8::try_from(name.len()).unwrap());
+    resource.extend_from_slice(name.as_bytes());
+    resource.resize(6 + (1 + name.len()).next_multiple_of(2), 0);
+    resource.extend_from_slice(&u32::try_from(data.len()).unwrap().to_be_bytes());
+    resource.extend_from_slice(data);
+    resource.resize(resource.len().next_multiple_of(2), 0);
+    resource
+}
+
+fn ifd(tag: Tag, field: Field) -> Ifd {
+    let mut fields: Fields = Fields::new();
+    fields.insert(tag, field);
+    Ifd {
+        fields,
+        offset: 0,
+        position: 0,
+        sub_ifds: Vec::new(),
+        exif: None,
+        gps: None,
+        interoperability: None,
+        duplicates: Vec::new(),
+    }
+}
+
+#[test]
+fn datasets_are_decoded() {
+    let bytes: Vec<u8> = [
+        dataset(1, 90, b"\x1B%G"),
+        dataset(2, 5, b"Title"),
+        dataset(2, 25, b"one"),
+        dataset(2, 25, b"two\0"),
+        dataset(2, 80, b"Photographer"),
+        dataset(2, 120, b"Caption"),
+        vec![0; 3],
+    ]
+    .concat();
+    let iptc: Iptc = Iptc::parse(&bytes).unwrap();
+    assert_eq!(iptc.object_name.as_deref(), Some("Title"));
+    assert_eq!(iptc.keywords, ["one", "two"]);
+    assert_eq!(iptc.by_line.as_deref(), Some("Photographer"));
+    assert_eq!(iptc.caption.as_deref(), Some("Caption"));
+    assert_eq!(iptc.headline, None);
+    assert_eq!(iptc.datasets.len(), 6);
+    assert_eq!(iptc.datasets[0], (1, 90, b"\x1B%G".to_vec()));
+}
+
+#[test]
+fn extended_datasets_are_decoded() {
+    // Length 0x8004 says the actual length takes the next four bytes
+    let caption: Vec<u8> = vec![b'a'; 40_000];
+    let mut bytes: Vec<u8> = vec![0x1C, 2, 120, 0x80, 0x04];
+    bytes.extend_from_slice(&u32::try_from(caption.len()).unwrap().to_be_bytes());
+    bytes.extend_from_slice(&caption);
+    bytes.extend_from_slice(&dataset(2, 105, b"Headline"));
+
+    let iptc: Iptc = Iptc::parse(&bytes).unwrap();
+    assert_eq!(
+        iptc.caption.map(|caption| caption.len()),
+        Some(caption.len())
+    );
+    assert_eq!(iptc.headline.as_deref(), Some("Headline"));
+
+    // Neither length may run past the end
+    assert!(matches!(
+        Iptc::parse(&bytes[..8]),
+        Err(TiffError::TruncatedValue { .. })
+    ));
+    assert!(matches!(
+        Iptc::parse(&bytes[..1000]),
+        Err(TiffError::TruncatedValue { .. })
+    ));
+}
+
+#[test]
+fn resources_are_split_past_padding() {
+    let bytes: Vec<u8> = [
+        // Empty name, padded to two bytes, odd sized data
+        resource(0x03ED, "", &[1, 2, 3]),
+        // Odd sized name, not padded, even sized data
+        resource(0x0404, "IPTC", &dataset(2, 5, b"Title")),
+        resource(0x0406, "Q", &[4, 5]),
+    ]
+    .concat();
+    assert_eq!(
+        photoshop_resources(&bytes).unwrap(),
+        [
+            (0x03ED, String::new(), vec![1, 2, 3]),
+            (0x0404, "IPTC".to_string(), dataset(2, 5, b"Title")),
+            (0x0406, "Q".to_string(), vec![4, 5]),
+        ]
+    );
+    assert!(photoshop_resources(&bytes[..bytes.len() - 1]).is_err());
+
+    let iptc: Option<Iptc> = ifd(Tag::Photoshop, Field::Byte(bytes)).iptc().unwrap();
+    assert_eq!(iptc.unwrap().object_name.as_deref(), Some("Title"));
+}
+
+#[test]
+fn long_iptcnaa_is_read_in_either_byte_order() {
+    // Padded to a whole number of LONGs
+    let bytes: Vec<u8> = [dataset(2, 5, b"Title"), dataset(2, 25, b"Key"), vec![0; 2]].concat();
+    for from_bytes in [u32::from_le_bytes, u32::from_be_bytes] {
+        let values: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(|chunk| from_bytes(chunk.try_into().unwrap()))
+            .collect();
+        let iptc: Iptc = ifd(Tag::IPTCNAA, Field::Long(values))
+            .iptc()
+            .unwrap()
+            .unwrap();
+        assert_eq!(iptc.object_name.as_deref(), Some("Title"));
+        assert_eq!(iptc.keywords, ["Key"]);
+    }
+
+    assert!(ifd(Tag::IPTCNAA, Field::Short(vec![0x1C02]))
+        .iptc()
+        .is_err());
+}
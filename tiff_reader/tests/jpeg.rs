@@ -0,0 +1,151 @@
+// © 𝟐𝟎𝟐𝟑 𝐆𝐮𝐢𝐥𝐡𝐞𝐫𝐦𝐞 𝐑𝐢𝐨𝐬 𝐀𝐥𝐥 𝐑𝐢𝐠𝐡𝐭𝐬 𝐑𝐞𝐬𝐞𝐫𝐯𝐞𝐝
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑓𝑟𝑒𝑒 𝑠𝑜𝑓𝑡𝑤𝑎𝑟𝑒: 𝑦𝑜𝑢 𝑐𝑎𝑛 𝑟𝑒𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒 𝑖𝑡 𝑎𝑛𝑑/𝑜𝑟 𝑚𝑜𝑑𝑖𝑓𝑦 𝑖𝑡 𝑢𝑛𝑑𝑒𝑟 𝑡ℎ𝑒 𝑡𝑒𝑟𝑚𝑠 𝑜𝑓 𝑡ℎ𝑒
+// 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑠 𝑝𝑢𝑏𝑙𝑖𝑠ℎ𝑒𝑑 𝑏𝑦 𝑡ℎ𝑒 𝐹𝑟𝑒𝑒 𝑆𝑜𝑓𝑡𝑤𝑎𝑟𝑒 𝐹𝑜𝑢𝑛𝑑𝑎𝑡𝑖𝑜𝑛, 𝑣𝑒𝑟𝑠𝑖𝑜𝑛 3 𝑜𝑓 𝑡ℎ𝑒
+// 𝐿𝑖𝑐𝑒𝑛𝑠𝑒.
+//
+// 𝑇ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚 𝑖𝑠 𝑑𝑖𝑠𝑡𝑟𝑖𝑏𝑢𝑡𝑒𝑑 𝑖𝑛 𝑡ℎ𝑒 ℎ𝑜𝑝𝑒 𝑡ℎ𝑎𝑡 𝑖𝑡 𝑤𝑖𝑙𝑙 𝑏𝑒 𝑢𝑠𝑒𝑓𝑢𝑙, 𝑏𝑢𝑡 𝑊𝐼𝑇𝐻𝑂𝑈𝑇 𝐴𝑁𝑌 𝑊𝐴𝑅𝑅𝐴𝑁𝑇𝑌;
+// 𝑤𝑖𝑡ℎ𝑜𝑢𝑡 𝑒𝑣𝑒𝑛 𝑡ℎ𝑒 𝑖𝑚𝑝𝑙𝑖𝑒𝑑 𝑤𝑎𝑟𝑟𝑎𝑛𝑡𝑦 𝑜𝑓 𝑀𝐸𝑅𝐶𝐻𝐴𝑁𝑇𝐴𝐵𝐼𝐿𝐼𝑇𝑌 𝑜𝑟 𝐹𝐼𝑇𝑁𝐸𝑆𝑆 𝐹𝑂𝑅 𝐴 𝑃𝐴𝑅𝑇𝐼𝐶𝑈𝐿𝐴𝑅 𝑃𝑈𝑅𝑃𝑂𝑆𝐸. 𝑆𝑒𝑒
+// 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑓𝑜𝑟 𝑚𝑜𝑟𝑒 𝑑𝑒𝑡𝑎𝑖𝑙𝑠.
+//
+// 𝑌𝑜𝑢 𝑠ℎ𝑜𝑢𝑙𝑑 ℎ𝑎𝑣𝑒 𝑟𝑒𝑐𝑒𝑖𝑣𝑒𝑑 𝑎 𝑐𝑜𝑝𝑦 𝑜𝑓 𝑡ℎ𝑒 𝐺𝑁𝑈 𝐺𝑒𝑛𝑒𝑟𝑎𝑙 𝑃𝑢𝑏𝑙𝑖𝑐 𝐿𝑖𝑐𝑒𝑛𝑠𝑒 𝑎𝑙𝑜𝑛𝑔 𝑤𝑖𝑡ℎ 𝑡ℎ𝑖𝑠 𝑝𝑟𝑜𝑔𝑟𝑎𝑚. 𝐼𝑓
+// 𝑛𝑜𝑡, 𝑠𝑒𝑒 ℎ𝑡𝑡𝑝://𝑤𝑤𝑤.𝑔𝑛𝑢.𝑜𝑟𝑔/𝑙𝑖𝑐𝑒𝑛𝑠𝑒𝑠/.
+
+use tiff_reader::{JpegComponent, JpegInfo, TiffError};
+
+const SOI: [u8; 2] = [0xFF, 0xD8];
+
+// A segment: marker, then its length, counting itself, and contents
+fn segment(marker: u8, contents: &[u8]) -> Vec<u8> {
+    let length: u16 = u16::try_from(contents.len() + 2).unwrap();
+    let mut segment: Vec<u8> = vec![0xFF, marker];
+    segment.extend_from_slice(&length.to_be_bytes());
+    segment.extend_from_slice(contents);
+    segment
+}
+
+// Baseline frame header, with components given as (id, horizontal, vertical)
+fn sof(height: u16, width: u16, components: &[(u8, u8, u8)]) -> Vec<u8> {
+    let mut contents: Vec<u8> = vec![8];
+    contents.extend_from_slice(&height.to_be_bytes());
+    contents.extend_from_slice(&width.to_be_bytes());
+    contents.push(u8::try_from(components.len()).unwrap());
+    for &(id, horizontal, vertical) in components {
+        contents.extend_from_slice(&[id, horizontal << 4 | vertical, 0]);
+    }
+    segment(0xC0, &contents)
+}
+
+fn jpeg(segments: &[Vec<u8>]) -> Vec<u8> {
+    let mut jpeg: Vec<u8> = SOI.to_vec();
+    for segment in segments {
+        jpeg.extend_from_slice(segment);
+    }
+    jpeg
+}
+
+fn malformed(bytes: &[u8]) -> bool {
+    matches!(JpegInfo::parse(bytes), Err(TiffError::InvalidData(_)))
+}
+
+#[test]
+fn frame_header_is_read() {
+    let info: JpegInfo = JpegInfo::parse(&jpeg(&[sof(480, 640, &[(1, 1, 1)])])).unwrap();
+    assert_eq!(
+        info,
+        JpegInfo {
+            marker: 0xC0,
+            precision: 8,
+            height: 480,
+            width: 640,
+            components: vec![JpegComponent {
+                id: 1,
+                horizontal: 1,
+                vertical: 1
+            }],
+        }
+    );
+    assert_eq!(info.process(), "baseline");
+}
+
+#[test]
+fn segments_before_the_frame_header_are_skipped() {
+    // APP0, APP1 and DQT, with contents that look like markers
+    let info: JpegInfo = JpegInfo::parse(&jpeg(&[
+        segment(0xE0, b"JFIF\0\x01\x02\0\0\x01\0\x01\0\0"),
+        segment(0xE1, &[0xFF, 0xC0, 0xFF, 0xDA]),
+        segment(0xDB, &[0; 65]),
+        sof(16, 32, &[(1, 1, 1)]),
+    ]))
+    .unwrap();
+    assert_eq!((info.width, info.height), (32, 16));
+}
+
+#[test]
+fn fill_bytes_are_skipped() {
+    let mut bytes: Vec<u8> = SOI.to_vec();
+    bytes.extend_from_slice(&[0xFF, 0xFF, 0xFF]);
+    bytes.extend_from_slice(&segment(0xE0, &[0; 4]));
+    bytes.extend_from_slice(&[0xFF, 0xFF]);
+    bytes.extend_from_slice(&sof(16, 32, &[(1, 1, 1)]));
+    assert_eq!(JpegInfo::parse(&bytes).unwrap().width, 32);
+}
+
+#[test]
+fn malformed_streams_are_rejected() {
+    // No SOI
+    assert!(malformed(&sof(16, 32, &[(1, 1, 1)])));
+    // Scan before the frame header
+    assert!(malformed(&jpeg(&[
+        segment(0xDA, &[1, 1, 0, 0, 0x3F, 0]),
+        sof(16, 32, &[(1, 1, 1)]),
+    ])));
+    // EOI before the frame header
+    assert!(malformed(&jpeg(&[vec![0xFF, 0xD9]])));
+    // Something other than a marker
+    assert!(malformed(&jpeg(&[vec![0x00, 0xC0]])));
+    // Segment running past the end
+    assert!(malformed(&jpeg(&[vec![0xFF, 0xE0, 0x01, 0x00, 0, 0]])));
+
+    // Frame header cut short, anywhere up to its last component
+    let complete: Vec<u8> = jpeg(&[sof(16, 32, &[(1, 2, 2), (2, 1, 1), (3, 1, 1)])]);
+    assert!(JpegInfo::parse(&complete).is_ok());
+    for length in 2..complete.len() {
+        assert!(malformed(&complete[..length]), "{length}");
+    }
+}
+
+#[test]
+fn height_may_be_left_to_dnl() {
+    let info: JpegInfo = JpegInfo::parse(&jpeg(&[sof(0, 32, &[(1, 1, 1)])])).unwrap();
+    assert_eq!((info.width, info.height), (32, 0));
+}
+
+#[test]
+fn subsampling_is_named() {
+    let subsampling = |luma: (u8, u8), chroma: (u8, u8)| {
+        JpegInfo::parse(&jpeg(&[sof(
+            16,
+            32,
+            &[
+                (1, luma.0, luma.1),
+                (2, chroma.0, chroma.1),
+                (3, chroma.0, chroma.1),
+            ],
+        )]))
+        .unwrap()
+        .subsampling()
+    };
+    assert_eq!(subsampling((1, 1), (1, 1)), Some("4:4:4"));
+    assert_eq!(subsampling((2, 2), (1, 1)), Some("4:2:0"));
+    assert_eq!(subsampling((2, 1), (1, 1)), Some("4:2:2"));
+    assert_eq!(subsampling((4, 1), (1, 1)), Some("4:1:1"));
+    assert_eq!(subsampling((3, 1), (1, 1)), None);
+
+    // Chroma components sampled differently, or only luma
+    let info: JpegInfo =
+        JpegInfo::parse(&jpeg(&[sof(16, 32, &[(1, 2, 2), (2, 1, 1), (3, 2, 1)])])).unwrap();
+    assert_eq!(info.subsampling(), None);
+    let info: JpegInfo = JpegInfo::parse(&jpeg(&[sof(16, 32, &[(1, 1, 1)])])).unwrap();
+    assert_eq!(info.subsampling(), None);
+}